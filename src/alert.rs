@@ -0,0 +1,273 @@
+use crate::app::App;
+use crate::config::AlertRule;
+use crate::data::MemoryData;
+use std::collections::VecDeque;
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+const LOG_SIZE: usize = 100;
+
+/// A value an alert rule can watch
+#[derive(Debug, Clone, PartialEq)]
+pub enum Metric {
+    Cpu,
+    Iowait,
+    Steal,
+    Memory,
+    Swap,
+    Load1,
+    Load5,
+    Load15,
+    Disk(Option<String>),  // Usage % of one mount, or the fullest one
+    DiskRead,
+    DiskWrite,
+    NetRx,
+    NetTx,
+    Temperature,  // Hottest sensor
+    PsiCpu,  // Pressure stall avg10, "some" unless named full
+    PsiMemory,
+    PsiMemoryFull,
+    PsiIo,
+    PsiIoFull,
+    Battery,
+}
+
+impl FromStr for Metric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Mount points are case-sensitive, only the prefix isn't
+        if s.get(..5).is_some_and(|prefix| prefix.eq_ignore_ascii_case("disk:")) {
+            return Ok(Metric::Disk(Some(s[5..].to_string())));
+        }
+
+        match s.to_lowercase().as_str() {
+            "cpu" => Ok(Metric::Cpu),
+            "iowait" => Ok(Metric::Iowait),
+            "steal" => Ok(Metric::Steal),
+            "memory" | "mem" => Ok(Metric::Memory),
+            "swap" => Ok(Metric::Swap),
+            "load1" => Ok(Metric::Load1),
+            "load5" => Ok(Metric::Load5),
+            "load15" => Ok(Metric::Load15),
+            "disk" => Ok(Metric::Disk(None)),
+            "disk_read" => Ok(Metric::DiskRead),
+            "disk_write" => Ok(Metric::DiskWrite),
+            "net_rx" => Ok(Metric::NetRx),
+            "net_tx" => Ok(Metric::NetTx),
+            "temperature" | "temp" => Ok(Metric::Temperature),
+            "battery" => Ok(Metric::Battery),
+            "psi_cpu" => Ok(Metric::PsiCpu),
+            "psi_memory" | "psi_mem" => Ok(Metric::PsiMemory),
+            "psi_memory_full" | "psi_mem_full" => Ok(Metric::PsiMemoryFull),
+            "psi_io" => Ok(Metric::PsiIo),
+            "psi_io_full" => Ok(Metric::PsiIoFull),
+            _ => Err(format!("unknown metric '{}'", s)),
+        }
+    }
+}
+
+impl Metric {
+    /// Current value, or None if the system doesn't report it
+    fn value(&self, app: &App) -> Option<f64> {
+        match self {
+            Metric::Cpu => Some(app.cpu_data.total_usage),
+            Metric::Iowait => app.cpu_data.times.map(|t| t.iowait),
+            Metric::Steal => app.cpu_data.times.map(|t| t.steal),
+            Metric::Memory => Some(app.memory_used().1),
+            Metric::Swap => (app.memory_data.swap_total > 0).then_some(app.memory_data.swap_percent),
+            Metric::Load1 => Some(app.load_avg.0),
+            Metric::Load5 => Some(app.load_avg.1),
+            Metric::Load15 => Some(app.load_avg.2),
+            Metric::Disk(Some(mount)) => app
+                .disk_data
+                .disk_info
+                .iter()
+                .find(|d| &d.mount_point == mount)
+                .map(|d| d.used_percent),
+            Metric::Disk(None) => app
+                .disk_data
+                .disk_info
+                .iter()
+                .map(|d| d.used_percent)
+                .reduce(f64::max),
+            Metric::DiskRead => Some(app.disk_data.read_rate as f64),
+            Metric::DiskWrite => Some(app.disk_data.write_rate as f64),
+            Metric::NetRx => Some(app.network_data.speed_down as f64),
+            Metric::NetTx => Some(app.network_data.speed_up as f64),
+            Metric::Temperature => app
+                .temperature_data
+                .sensors
+                .iter()
+                .map(|s| s.temperature as f64)
+                .reduce(f64::max),
+            Metric::Battery => app.battery_data.batteries.first().map(|b| b.percentage as f64),
+            Metric::PsiCpu => app.pressure_data.cpu.map(|p| p.some.avg10),
+            Metric::PsiMemory => app.pressure_data.memory.map(|p| p.some.avg10),
+            Metric::PsiMemoryFull => app.pressure_data.memory.and_then(|p| p.full).map(|avg| avg.avg10),
+            Metric::PsiIo => app.pressure_data.io.map(|p| p.some.avg10),
+            Metric::PsiIoFull => app.pressure_data.io.and_then(|p| p.full).map(|avg| avg.avg10),
+        }
+    }
+
+    /// Format a value in the metric's unit
+    pub fn format(&self, value: f64) -> String {
+        match self {
+            Metric::Load1 | Metric::Load5 | Metric::Load15 => format!("{:.2}", value),
+            Metric::DiskRead | Metric::DiskWrite | Metric::NetRx | Metric::NetTx => {
+                format!("{}/s", MemoryData::format_bytes(value as u64))
+            }
+            Metric::Temperature => format!("{:.0}°C", value),
+            _ => format!("{:.1}%", value),
+        }
+    }
+}
+
+/// A configured rule and its evaluation state
+pub struct AlertState {
+    pub rule: AlertRule,
+    pub metric: Metric,
+    pub value: Option<f64>,
+    pub firing: bool,
+    breached_since: Option<Instant>,
+}
+
+impl AlertState {
+    /// The rule's name, or its condition if unnamed
+    pub fn label(&self) -> String {
+        self.rule.name.clone().unwrap_or_else(|| self.condition())
+    }
+
+    pub fn condition(&self) -> String {
+        format!(
+            "{} {} {}",
+            self.rule.metric,
+            self.rule.comparison.symbol(),
+            self.metric.format(self.rule.threshold)
+        )
+    }
+}
+
+pub struct AlertEvent {
+    pub time: Instant,
+    pub firing: bool,  // false = resolved
+    pub message: String,
+}
+
+#[derive(Default)]
+pub struct Alerts {
+    pub rules: Vec<AlertState>,
+    pub log: VecDeque<AlertEvent>,  // Newest first
+    bell_pending: bool,
+}
+
+impl Alerts {
+    /// Build from config rules, skipping any with an unknown metric
+    pub fn new(rules: &[AlertRule]) -> Self {
+        let rules = rules
+            .iter()
+            .filter_map(|rule| {
+                let metric = rule.metric.parse().ok()?;
+                Some(AlertState {
+                    rule: rule.clone(),
+                    metric,
+                    value: None,
+                    firing: false,
+                    breached_since: None,
+                })
+            })
+            .collect();
+
+        Self {
+            rules,
+            ..Self::default()
+        }
+    }
+
+    pub fn firing_count(&self) -> usize {
+        self.rules.iter().filter(|r| r.firing).count()
+    }
+
+    /// Whether an alert asked for the terminal bell since the last call
+    pub fn take_bell(&mut self) -> bool {
+        std::mem::take(&mut self.bell_pending)
+    }
+
+    /// Check every rule against the app's latest sample
+    pub fn evaluate(&mut self, app: &App) {
+        let now = Instant::now();
+        let mut events = Vec::new();
+
+        for state in &mut self.rules {
+            state.value = state.metric.value(app);
+            let breached = state
+                .value
+                .is_some_and(|v| state.rule.comparison.holds(v, state.rule.threshold));
+
+            if !breached {
+                state.breached_since = None;
+                if state.firing {
+                    state.firing = false;
+                    events.push(AlertEvent {
+                        time: now,
+                        firing: false,
+                        message: format!("{} resolved", state.label()),
+                    });
+                }
+                continue;
+            }
+
+            let since = *state.breached_since.get_or_insert(now);
+            let sustained = now.duration_since(since) >= Duration::from_secs(state.rule.duration);
+            if !sustained || state.firing {
+                continue;
+            }
+
+            state.firing = true;
+            let value = state.value.unwrap_or_default();
+            events.push(AlertEvent {
+                time: now,
+                firing: true,
+                message: format!("{} ({})", state.label(), state.metric.format(value)),
+            });
+
+            if state.rule.bell {
+                self.bell_pending = true;
+            }
+            if let Some(command) = &state.rule.command {
+                run_command(command, state, value);
+            }
+        }
+
+        for event in events {
+            if self.log.len() >= LOG_SIZE {
+                self.log.pop_back();
+            }
+            self.log.push_front(event);
+        }
+    }
+}
+
+/// Run an alert's command in the background with details in the environment
+fn run_command(command: &str, state: &AlertState, value: f64) {
+    // Output would draw over the UI, so it is discarded
+    let child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .env("MPROBE_ALERT", state.label())
+        .env("MPROBE_METRIC", &state.rule.metric)
+        .env("MPROBE_VALUE", value.to_string())
+        .env("MPROBE_THRESHOLD", state.rule.threshold.to_string())
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+
+    // Reap the child without blocking the update loop
+    if let Ok(mut child) = child {
+        std::thread::spawn(move || {
+            let _ = child.wait();
+        });
+    }
+}
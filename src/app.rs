@@ -1,9 +1,16 @@
+use crate::alert::Alerts;
 use crate::config::Config;
-use crate::data::{CpuData, DiskData, MemoryData, NetworkData, ProcessData, ProcessInfo, SortColumn, TemperatureData, BatteryData, ConnectionData};
+use crate::layout::PanelLayout;
+use crate::ui::ThemeSet;
+use crate::data::{CgroupData, CpuData, CpuTimes, DiskData, MemoryData, NetworkData, PressureData, ProcessData, ProcessInfo, ProcessNetData, ProcessOptions, ProcessView, SortColumn, TemperatureData, BatteryData, ConnectionData, UsedMemory};
+use crate::source::{DataSource, LiveSource};
 use sysinfo::{Signal, System};
-use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet, VecDeque};
 
-const GRAPH_HISTORY_SIZE: usize = 120;
+pub const GRAPH_HISTORY_SIZE: usize = 120;
+
+/// Full-screen views, in tab bar order
+pub const TABS: [&str; 4] = ["Overview", "Processes", "Network", "Disks"];
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum KillSignal {
@@ -12,6 +19,8 @@ pub enum KillSignal {
 }
 
 pub struct App {
+    // Taken out while refreshing, since sources write into the app
+    source: Option<Box<dyn DataSource>>,
     pub system: System,
     pub cpu_data: CpuData,
     pub disk_data: DiskData,
@@ -19,17 +28,39 @@ pub struct App {
     pub network_data: NetworkData,
     pub process_data: ProcessData,
     pub temperature_data: TemperatureData,
+    pub pressure_data: PressureData,
+    pub cgroup_data: CgroupData,
     pub battery_data: BatteryData,
     pub connection_data: ConnectionData,
+    pub process_net_data: ProcessNetData,
     pub cpu_history: VecDeque<f64>,
+    pub core_history: Vec<VecDeque<f64>>,
+    pub cpu_times_history: VecDeque<CpuTimes>,
     pub mem_history: VecDeque<f64>,
     pub net_up_history: VecDeque<u64>,
     pub net_down_history: VecDeque<u64>,
+    pub disk_read_history: VecDeque<u64>,
+    pub disk_write_history: VecDeque<u64>,
+    // "some" avg10 stall percentage for cpu, memory and io
+    pub pressure_history: [VecDeque<f64>; 3],
+    // Per-interface (up, down) speed history
+    pub iface_history: HashMap<String, (VecDeque<u64>, VecDeque<u64>)>,
+    // Interface shown in the network panel (None = all)
+    pub selected_interface: Option<String>,
     pub selected_tab: usize,
     pub process_scroll: usize,
     pub filter_mode: bool,
     pub filter_text: String,
-    pub tree_view: bool,
+    pub process_view: ProcessView,
+    // Cgroups whose member processes are listed in the cgroup view
+    pub expanded_cgroups: HashSet<String>,
+    // Processes whose descendants are folded into their row in the tree view
+    pub collapsed_pids: HashSet<u32>,
+    pub io_only: bool,
+    // CPU panel shows a per-core grid instead of the total graph
+    pub per_core_view: bool,
+    // Definition of "used" memory shown in the UI
+    pub used_memory: UsedMemory,
     pub sort_column: SortColumn,
     pub sort_ascending: bool,
     pub hostname: String,
@@ -45,12 +76,25 @@ pub struct App {
     pub show_help: bool,
     // View mode: false = processes, true = connections
     pub show_connections: bool,
+    // Bottom panel shows the alert log instead of processes
+    pub show_alerts: bool,
+    pub alerts: Alerts,
+    // Panels on the overview tab
+    pub layout: PanelLayout,
     // CLI options
     pub no_color: bool,
+    pub themes: ThemeSet,
+    // Replay playback
+    pub paused: bool,
+    pub replay_speed: f64,
 }
 
 impl App {
     pub fn new() -> Self {
+        Self::with_source(Box::new(LiveSource))
+    }
+
+    pub fn with_source(source: Box<dyn DataSource>) -> Self {
         let mut system = System::new_all();
         system.refresh_all();
 
@@ -59,6 +103,7 @@ impl App {
         let kernel_version = System::kernel_version().unwrap_or_else(|| "Unknown".to_string());
 
         let mut app = Self {
+            source: Some(source),
             system,
             cpu_data: CpuData::default(),
             disk_data: DiskData::default(),
@@ -66,17 +111,32 @@ impl App {
             network_data: NetworkData::default(),
             process_data: ProcessData::default(),
             temperature_data: TemperatureData::default(),
+            pressure_data: PressureData::default(),
+            cgroup_data: CgroupData::default(),
             battery_data: BatteryData::default(),
             connection_data: ConnectionData::default(),
+            process_net_data: ProcessNetData::default(),
             cpu_history: VecDeque::with_capacity(GRAPH_HISTORY_SIZE),
+            core_history: Vec::new(),
+            cpu_times_history: VecDeque::with_capacity(GRAPH_HISTORY_SIZE),
             mem_history: VecDeque::with_capacity(GRAPH_HISTORY_SIZE),
             net_up_history: VecDeque::with_capacity(GRAPH_HISTORY_SIZE),
             net_down_history: VecDeque::with_capacity(GRAPH_HISTORY_SIZE),
+            disk_read_history: VecDeque::with_capacity(GRAPH_HISTORY_SIZE),
+            disk_write_history: VecDeque::with_capacity(GRAPH_HISTORY_SIZE),
+            pressure_history: Default::default(),
+            iface_history: HashMap::new(),
+            selected_interface: None,
             selected_tab: 0,
             process_scroll: 0,
             filter_mode: false,
             filter_text: String::new(),
-            tree_view: false,
+            process_view: ProcessView::default(),
+            expanded_cgroups: HashSet::new(),
+            collapsed_pids: HashSet::new(),
+            io_only: false,
+            per_core_view: false,
+            used_memory: UsedMemory::default(),
             sort_column: SortColumn::Cpu,
             sort_ascending: false,
             hostname,
@@ -88,76 +148,208 @@ impl App {
             status_message: None,
             show_help: false,
             show_connections: false,
+            show_alerts: false,
+            alerts: Alerts::default(),
+            layout: PanelLayout::default(),
             no_color: false,
+            themes: ThemeSet::default(),
+            paused: false,
+            replay_speed: 1.0,
         };
 
-        // Initialize with zeros
-        for _ in 0..GRAPH_HISTORY_SIZE {
-            app.cpu_history.push_back(0.0);
-            app.mem_history.push_back(0.0);
-            app.net_up_history.push_back(0);
-            app.net_down_history.push_back(0);
-        }
-
+        app.reset_history();
         app.update();
         app
     }
 
-    pub fn update(&mut self) {
-        self.system.refresh_all();
-        self.uptime = System::uptime();
+    /// Clear graph history back to all zeros
+    pub fn reset_history(&mut self) {
+        self.cpu_history = VecDeque::from(vec![0.0; GRAPH_HISTORY_SIZE]);
+        self.core_history.clear();
+        self.cpu_times_history = VecDeque::from(vec![CpuTimes::default(); GRAPH_HISTORY_SIZE]);
+        self.mem_history = VecDeque::from(vec![0.0; GRAPH_HISTORY_SIZE]);
+        self.net_up_history = VecDeque::from(vec![0; GRAPH_HISTORY_SIZE]);
+        self.net_down_history = VecDeque::from(vec![0; GRAPH_HISTORY_SIZE]);
+        self.disk_read_history = VecDeque::from(vec![0; GRAPH_HISTORY_SIZE]);
+        self.disk_write_history = VecDeque::from(vec![0; GRAPH_HISTORY_SIZE]);
+        self.pressure_history = std::array::from_fn(|_| VecDeque::from(vec![0.0; GRAPH_HISTORY_SIZE]));
+        self.iface_history.clear();
+    }
 
-        // Update load average
-        let load = System::load_average();
-        self.load_avg = (load.one, load.five, load.fifteen);
+    pub fn update(&mut self) {
+        if let Some(mut source) = self.source.take() {
+            let changed = source.refresh(self);
+            self.source = Some(source);
+
+            if changed {
+                self.record_history();
+
+                // Forget processes that exited, so a reused PID doesn't start collapsed
+                let processes = &self.process_data;
+                self.collapsed_pids.retain(|&pid| processes.has_process(pid));
+
+                // Sustained durations are wall-clock based, so recordings aren't evaluated
+                if !self.is_replay() {
+                    let mut alerts = std::mem::take(&mut self.alerts);
+                    alerts.evaluate(self);
+                    self.alerts = alerts;
+                }
+            }
+        }
+    }
 
-        // Update CPU data
-        self.cpu_data.update(&self.system);
+    /// Append the current sample to every graph history
+    pub fn record_history(&mut self) {
         if self.cpu_history.len() >= GRAPH_HISTORY_SIZE {
             self.cpu_history.pop_front();
         }
         self.cpu_history.push_back(self.cpu_data.total_usage);
 
-        // Update Memory data
-        self.memory_data.update(&self.system);
+        if self.cpu_times_history.len() >= GRAPH_HISTORY_SIZE {
+            self.cpu_times_history.pop_front();
+        }
+        self.cpu_times_history.push_back(self.cpu_data.times.unwrap_or_default());
+
+        // Per-core history, resized if the core count changes (e.g. CPU hotplug)
+        self.core_history
+            .resize_with(self.cpu_data.per_core_usage.len(), || VecDeque::from(vec![0.0; GRAPH_HISTORY_SIZE]));
+        for (history, &usage) in self.core_history.iter_mut().zip(&self.cpu_data.per_core_usage) {
+            if history.len() >= GRAPH_HISTORY_SIZE {
+                history.pop_front();
+            }
+            history.push_back(usage);
+        }
+
         if self.mem_history.len() >= GRAPH_HISTORY_SIZE {
             self.mem_history.pop_front();
         }
-        self.mem_history.push_back(self.memory_data.used_percent);
-
-        // Update Disk data
-        self.disk_data.update();
+        self.mem_history.push_back(self.memory_used().1);
 
-        // Update Temperature data
-        self.temperature_data.update();
+        if self.disk_read_history.len() >= GRAPH_HISTORY_SIZE {
+            self.disk_read_history.pop_front();
+        }
+        if self.disk_write_history.len() >= GRAPH_HISTORY_SIZE {
+            self.disk_write_history.pop_front();
+        }
+        self.disk_read_history.push_back(self.disk_data.read_rate);
+        self.disk_write_history.push_back(self.disk_data.write_rate);
 
-        // Update Battery data
-        self.battery_data.update();
+        for (history, (_, pressure)) in self.pressure_history.iter_mut().zip(self.pressure_data.resources()) {
+            if history.len() >= GRAPH_HISTORY_SIZE {
+                history.pop_front();
+            }
+            history.push_back(pressure.map(|p| p.some.avg10).unwrap_or(0.0));
+        }
 
-        // Update Network data
-        let (up, down) = self.network_data.update(&self.system);
         if self.net_up_history.len() >= GRAPH_HISTORY_SIZE {
             self.net_up_history.pop_front();
         }
         if self.net_down_history.len() >= GRAPH_HISTORY_SIZE {
             self.net_down_history.pop_front();
         }
-        self.net_up_history.push_back(up);
-        self.net_down_history.push_back(down);
+        self.net_up_history.push_back(self.network_data.speed_up);
+        self.net_down_history.push_back(self.network_data.speed_down);
+
+        // Per-interface history, dropping interfaces that disappeared
+        self.iface_history
+            .retain(|name, _| self.network_data.interfaces.iter().any(|iface| &iface.name == name));
+        for iface in &self.network_data.interfaces {
+            let (up_history, down_history) = self
+                .iface_history
+                .entry(iface.name.clone())
+                .or_insert_with(|| {
+                    (
+                        VecDeque::from(vec![0; GRAPH_HISTORY_SIZE]),
+                        VecDeque::from(vec![0; GRAPH_HISTORY_SIZE]),
+                    )
+                });
+            if up_history.len() >= GRAPH_HISTORY_SIZE {
+                up_history.pop_front();
+            }
+            if down_history.len() >= GRAPH_HISTORY_SIZE {
+                down_history.pop_front();
+            }
+            up_history.push_back(iface.speed_up);
+            down_history.push_back(iface.speed_down);
+        }
+        if let Some(name) = &self.selected_interface {
+            if self.network_data.interface(name).is_none() {
+                self.selected_interface = None;
+            }
+        }
+    }
+
+    /// Current sample and total when replaying a recording
+    pub fn replay_position(&self) -> Option<(usize, usize)> {
+        self.source.as_ref().and_then(|source| source.position())
+    }
+
+    pub fn is_replay(&self) -> bool {
+        self.replay_position().is_some()
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
 
-        // Update Connection data (less frequently - every update is fine)
-        self.connection_data.update();
+    /// Step the replay by `delta` samples (pauses playback)
+    pub fn step_replay(&mut self, delta: isize) {
+        let Some((current, _)) = self.replay_position() else {
+            return;
+        };
 
-        // Update Process data
-        self.process_data.update(&self.system, &self.filter_text, self.sort_column, self.sort_ascending, self.tree_view);
+        self.paused = true;
+        let target = (current as isize - 1 + delta).max(0) as usize;
+        if let Some(mut source) = self.source.take() {
+            source.seek(self, target);
+            self.source = Some(source);
+        }
+    }
+
+    /// Double or halve the replay speed, between 1/4x and 16x
+    pub fn change_replay_speed(&mut self, faster: bool) {
+        self.replay_speed = if faster {
+            (self.replay_speed * 2.0).min(16.0)
+        } else {
+            (self.replay_speed / 2.0).max(0.25)
+        };
     }
 
     pub fn next_tab(&mut self) {
-        self.selected_tab = (self.selected_tab + 1) % 4;
+        self.selected_tab = (self.selected_tab + 1) % TABS.len();
     }
 
     pub fn prev_tab(&mut self) {
-        self.selected_tab = if self.selected_tab == 0 { 3 } else { self.selected_tab - 1 };
+        self.selected_tab = if self.selected_tab == 0 { TABS.len() - 1 } else { self.selected_tab - 1 };
+    }
+
+    pub fn select_tab(&mut self, index: usize) {
+        if index < TABS.len() {
+            self.selected_tab = index;
+        }
+    }
+
+    /// Cycle the network panel between all interfaces and each single one
+    pub fn cycle_interface(&mut self) {
+        let names: Vec<&String> = self.network_data.interfaces.iter().map(|iface| &iface.name).collect();
+
+        self.selected_interface = match &self.selected_interface {
+            None => names.first().map(|name| name.to_string()),
+            Some(current) => names
+                .iter()
+                .position(|name| *name == current)
+                .and_then(|i| names.get(i + 1))
+                .map(|name| name.to_string()),
+        };
+    }
+
+    /// Speed history (up, down) for the interface shown in the network panel
+    pub fn net_history(&self) -> (&VecDeque<u64>, &VecDeque<u64>) {
+        self.selected_interface
+            .as_ref()
+            .and_then(|name| self.iface_history.get(name))
+            .map(|(up, down)| (up, down))
+            .unwrap_or((&self.net_up_history, &self.net_down_history))
     }
 
     pub fn scroll_up(&mut self) {
@@ -188,21 +380,127 @@ impl App {
     pub fn add_filter_char(&mut self, c: char) {
         self.filter_text.push(c);
         self.process_scroll = 0;
+        self.arrange_processes();
     }
 
     pub fn remove_filter_char(&mut self) {
         self.filter_text.pop();
         self.process_scroll = 0;
+        self.arrange_processes();
     }
 
     pub fn clear_filter(&mut self) {
         self.filter_text.clear();
         self.filter_mode = false;
         self.process_scroll = 0;
+        self.arrange_processes();
     }
 
     pub fn toggle_tree_view(&mut self) {
-        self.tree_view = !self.tree_view;
+        self.set_process_view(ProcessView::Tree);
+    }
+
+    /// Switch between the flat list and processes grouped by cgroup
+    pub fn toggle_cgroup_view(&mut self) {
+        self.set_process_view(ProcessView::Cgroups);
+    }
+
+    /// Switch to `view`, or back to the flat list if it's already shown
+    fn set_process_view(&mut self, view: ProcessView) {
+        self.process_view = if self.process_view == view { ProcessView::List } else { view };
+        self.process_scroll = 0;
+        self.arrange_processes();
+    }
+
+    /// Expand or collapse the selected tree node or cgroup
+    pub fn toggle_expanded(&mut self) {
+        match self.process_view {
+            ProcessView::Tree => self.toggle_collapsed_process(),
+            ProcessView::Cgroups => self.toggle_expanded_cgroup(),
+            ProcessView::List => {}
+        }
+    }
+
+    fn toggle_collapsed_process(&mut self) {
+        let Some(proc) = self.process_data.processes.get(self.process_scroll) else {
+            return;
+        };
+
+        // On a process without children, collapse its parent
+        let pid = match (&proc.tree, proc.parent_pid) {
+            (Some(node), _) if node.has_children => proc.pid,
+            (Some(_), Some(parent)) if proc.depth > 0 => parent,
+            _ => return,
+        };
+        if !self.collapsed_pids.remove(&pid) {
+            self.collapsed_pids.insert(pid);
+        }
+        self.arrange_processes();
+
+        if let Some(row) = self.process_data.processes.iter().position(|p| p.pid == pid) {
+            self.process_scroll = row;
+        }
+    }
+
+    fn toggle_expanded_cgroup(&mut self) {
+        let Some(proc) = self.process_data.processes.get(self.process_scroll) else {
+            return;
+        };
+
+        // On a member process, collapse the group it belongs to
+        let key = proc.group_key().to_string();
+        if proc.group.is_none() || self.expanded_cgroups.contains(&key) {
+            self.expanded_cgroups.remove(&key);
+        } else {
+            self.expanded_cgroups.insert(key.clone());
+        }
+        self.arrange_processes();
+
+        if let Some(row) = self
+            .process_data
+            .processes
+            .iter()
+            .position(|p| p.group.is_some() && p.group_key() == key)
+        {
+            self.process_scroll = row;
+        }
+    }
+
+    /// Rebuild the process list for the current view without collecting new data
+    fn arrange_processes(&mut self) {
+        let options = ProcessOptions {
+            filter: &self.filter_text,
+            sort_column: self.sort_column,
+            sort_ascending: self.sort_ascending,
+            view: self.process_view,
+            io_only: self.io_only,
+            expanded_cgroups: &self.expanded_cgroups,
+            collapsed_pids: &self.collapsed_pids,
+        };
+        self.process_data.arrange(&options);
+    }
+
+    /// Switch the CPU panel between the total graph and a per-core grid
+    pub fn toggle_per_core_view(&mut self) {
+        self.per_core_view = !self.per_core_view;
+    }
+
+    /// Switch between definitions of "used" memory
+    pub fn cycle_used_memory(&mut self) {
+        self.used_memory = self.used_memory.next();
+        self.set_status(format!("Memory used: {}", self.used_memory.label()));
+    }
+
+    /// Used memory bytes and percent under the selected definition
+    pub fn memory_used(&self) -> (u64, f64) {
+        self.memory_data.used_as(self.used_memory)
+    }
+
+    /// Toggle showing only processes doing disk I/O
+    pub fn toggle_io_only(&mut self) {
+        self.io_only = !self.io_only;
+        self.process_scroll = 0;
+        self.arrange_processes();
     }
 
     pub fn cycle_sort(&mut self) {
@@ -210,14 +508,20 @@ impl App {
             SortColumn::Pid => SortColumn::Name,
             SortColumn::Name => SortColumn::Cpu,
             SortColumn::Cpu => SortColumn::Memory,
-            SortColumn::Memory => SortColumn::Pid,
+            SortColumn::Memory => SortColumn::NetRx,
+            SortColumn::NetRx => SortColumn::NetTx,
+            SortColumn::NetTx => SortColumn::DiskRead,
+            SortColumn::DiskRead => SortColumn::DiskWrite,
+            SortColumn::DiskWrite => SortColumn::Pid,
         };
         self.process_scroll = 0;
+        self.arrange_processes();
     }
 
     pub fn toggle_sort_order(&mut self) {
         self.sort_ascending = !self.sort_ascending;
         self.process_scroll = 0;
+        self.arrange_processes();
     }
 
     pub fn get_filtered_processes(&self) -> &[ProcessInfo] {
@@ -240,7 +544,17 @@ impl App {
 
     /// Initiate kill confirmation for the selected process
     pub fn initiate_kill(&mut self, signal: KillSignal) {
+        // Recorded PIDs may belong to unrelated processes by now
+        if self.is_replay() {
+            self.set_status("Signals are disabled during replay".to_string());
+            return;
+        }
+
         if let Some(proc) = self.process_data.processes.get(self.process_scroll) {
+            if proc.group.is_some() {
+                self.set_status("Select a process in the group to send a signal".to_string());
+                return;
+            }
             self.kill_confirm = Some((proc.pid, proc.name.clone(), signal));
         }
     }
@@ -293,6 +607,17 @@ impl App {
         }
     }
 
+    /// Switch to the next color theme
+    pub fn cycle_theme(&mut self) {
+        if self.no_color {
+            self.set_status("Colors are disabled".to_string());
+            return;
+        }
+
+        self.themes.cycle();
+        self.set_status(format!("Theme: {}", self.themes.current_name()));
+    }
+
     /// Toggle help screen
     pub fn toggle_help(&mut self) {
         self.show_help = !self.show_help;
@@ -301,6 +626,19 @@ impl App {
     /// Toggle connections view
     pub fn toggle_connections(&mut self) {
         self.show_connections = !self.show_connections;
+        self.show_alerts = false;
+    }
+
+    /// Toggle alert log view
+    pub fn toggle_alerts(&mut self) {
+        self.show_alerts = !self.show_alerts;
+        self.show_connections = false;
+    }
+
+    /// Load the configured alert rules. Only the terminal UI does this, so
+    /// `record`, `serve` and snapshots never run alert commands
+    pub fn load_alerts(&mut self, config: &Config) {
+        self.alerts = Alerts::new(&config.alerts);
     }
 
     /// Apply settings from config file
@@ -311,8 +649,15 @@ impl App {
             "name" => SortColumn::Name,
             "cpu" => SortColumn::Cpu,
             "memory" | "mem" => SortColumn::Memory,
+            "net_rx" | "rx" => SortColumn::NetRx,
+            "net_tx" | "tx" => SortColumn::NetTx,
+            "disk_read" | "read" => SortColumn::DiskRead,
+            "disk_write" | "write" => SortColumn::DiskWrite,
             _ => SortColumn::Cpu,
         };
         self.sort_ascending = config.sort_ascending;
+        self.network_data.set_excluded(&config.network_exclude);
+        // Config::load already reported an invalid layout
+        self.layout = PanelLayout::from_config(&config.layout).unwrap_or_default();
     }
 }
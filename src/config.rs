@@ -1,3 +1,5 @@
+use crate::alert::Metric;
+use crate::layout::PanelLayout;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
@@ -9,10 +11,128 @@ pub struct Config {
     pub update_interval: u64,
     /// Disable colors
     pub no_color: bool,
-    /// Default sort column (pid, name, cpu, memory)
+    /// Color theme: adwaita, gruvbox, nord, solarized-light or a file in the themes directory
+    pub theme: String,
+    /// Default sort column (pid, name, cpu, memory, net_rx, net_tx, disk_read, disk_write)
     pub sort_by: String,
     /// Sort ascending
     pub sort_ascending: bool,
+    /// Interfaces left out of network totals; a trailing `*` matches a prefix (e.g. "lo", "veth*")
+    pub network_exclude: Vec<String>,
+    /// Threshold alerts, one `[[alerts]]` table each
+    pub alerts: Vec<AlertRule>,
+    /// Panels shown on the overview tab
+    pub layout: LayoutConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LayoutConfig {
+    /// Rows from top to bottom, below the header
+    pub rows: Vec<RowConfig>,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        let row = |height: &str, panels: &[(&str, &str)]| RowConfig {
+            height: height.to_string(),
+            panels: panels
+                .iter()
+                .map(|(kind, width)| PanelConfig {
+                    kind: kind.to_string(),
+                    width: width.to_string(),
+                })
+                .collect(),
+        };
+
+        Self {
+            rows: vec![
+                row("11", &[("cpu", "50%"), ("memory", "50%")]),
+                row("9", &[("network", "33%"), ("disk", "34%"), ("system", "33%")]),
+                row("8+", &[("processes", "*")]),
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RowConfig {
+    /// "11" (rows), "30%", "8+" (at least 8 rows) or "*" (share what's left)
+    pub height: String,
+    pub panels: Vec<PanelConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PanelConfig {
+    /// cpu, memory, network, disk, system, sensors, pressure, processes, connections or alerts
+    pub kind: String,
+    /// Same syntax as the row height, in columns
+    #[serde(default = "PanelConfig::default_width")]
+    pub width: String,
+}
+
+impl PanelConfig {
+    fn default_width() -> String {
+        "*".to_string()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertRule {
+    /// Shown in the alert panel; defaults to the condition itself
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// What to watch (cpu, iowait, steal, memory, swap, load1, load5, load15, disk,
+    /// disk:<mount>, disk_read, disk_write, net_rx, net_tx, temperature, battery,
+    /// psi_cpu, psi_memory, psi_memory_full, psi_io, psi_io_full)
+    pub metric: String,
+    /// One of >, >=, <, <=
+    #[serde(default)]
+    pub comparison: Comparison,
+    /// Percent for usage metrics, bytes/s for rates, °C for temperature
+    pub threshold: f64,
+    /// Seconds the condition must hold before the alert fires
+    #[serde(default)]
+    pub duration: u64,
+    /// Shell command run when the alert fires
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Ring the terminal bell when the alert fires
+    #[serde(default)]
+    pub bell: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Comparison {
+    #[default]
+    #[serde(rename = ">")]
+    Above,
+    #[serde(rename = ">=")]
+    AtLeast,
+    #[serde(rename = "<")]
+    Below,
+    #[serde(rename = "<=")]
+    AtMost,
+}
+
+impl Comparison {
+    pub fn holds(self, value: f64, threshold: f64) -> bool {
+        match self {
+            Comparison::Above => value > threshold,
+            Comparison::AtLeast => value >= threshold,
+            Comparison::Below => value < threshold,
+            Comparison::AtMost => value <= threshold,
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Comparison::Above => ">",
+            Comparison::AtLeast => ">=",
+            Comparison::Below => "<",
+            Comparison::AtMost => "<=",
+        }
+    }
 }
 
 impl Default for Config {
@@ -20,8 +140,12 @@ impl Default for Config {
         Self {
             update_interval: 2000,
             no_color: false,
+            theme: "adwaita".to_string(),
             sort_by: "cpu".to_string(),
             sort_ascending: false,
+            network_exclude: Vec::new(),
+            alerts: Vec::new(),
+            layout: LayoutConfig::default(),
         }
     }
 }
@@ -32,6 +156,11 @@ impl Config {
         dirs::config_dir().map(|p| p.join("mprobe").join("config.toml"))
     }
 
+    /// Get the directory user themes are loaded from (~/.config/mprobe/themes)
+    pub fn themes_dir() -> Option<PathBuf> {
+        dirs::config_dir().map(|p| p.join("mprobe").join("themes"))
+    }
+
     /// Load config from file, returning default if file doesn't exist
     pub fn load() -> Self {
         let Some(path) = Self::config_path() else {
@@ -43,8 +172,22 @@ impl Config {
         }
 
         match fs::read_to_string(&path) {
-            Ok(content) => match toml::from_str(&content) {
-                Ok(config) => config,
+            Ok(content) => match toml::from_str::<Self>(&content) {
+                Ok(mut config) => {
+                    // Drop alerts on unknown metrics rather than the whole config
+                    config.alerts.retain(|rule| match rule.metric.parse::<Metric>() {
+                        Ok(_) => true,
+                        Err(e) => {
+                            eprintln!("Warning: Ignoring alert: {}", e);
+                            false
+                        }
+                    });
+                    if let Err(e) = PanelLayout::from_config(&config.layout) {
+                        eprintln!("Warning: Invalid layout, using the default: {}", e);
+                        config.layout = LayoutConfig::default();
+                    }
+                    config
+                }
                 Err(e) => {
                     eprintln!("Warning: Failed to parse config file: {}", e);
                     Self::default()
@@ -1,9 +1,10 @@
+use serde::{Deserialize, Serialize};
 #[cfg(target_os = "linux")]
 use std::fs;
 #[cfg(target_os = "linux")]
 use std::path::Path;
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct BatteryInfo {
     pub percentage: f32,
     pub state: BatteryState,
@@ -11,7 +12,7 @@ pub struct BatteryInfo {
     pub time_to_full: Option<u64>,   // seconds
 }
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum BatteryState {
     Charging,
     Discharging,
@@ -32,7 +33,7 @@ impl std::fmt::Display for BatteryState {
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct BatteryData {
     pub batteries: Vec<BatteryInfo>,
     pub has_battery: bool,
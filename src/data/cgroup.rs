@@ -0,0 +1,292 @@
+use serde::{Deserialize, Serialize};
+#[cfg(target_os = "linux")]
+use std::path::{Path, PathBuf};
+#[cfg(target_os = "linux")]
+use std::time::Instant;
+
+/// Limits of the cgroup (v2) mprobe runs in, and whether it looks like a container.
+/// Limits are the tightest along the path up to the root, with usage read from the
+/// cgroup that sets each one; None means unlimited.
+#[derive(Default, Serialize, Deserialize)]
+pub struct CgroupData {
+    #[cfg(target_os = "linux")]
+    #[serde(skip)]
+    dir: Option<PathBuf>,
+    #[serde(skip)]
+    detected: bool,
+    #[cfg(target_os = "linux")]
+    #[serde(skip)]
+    prev_usage: Option<(u64, Instant, PathBuf)>,  // cpu.stat usage, when, and which cgroup
+    #[serde(skip)]
+    pub memory_stat: Option<CgroupMemoryStat>,
+    pub in_container: bool,
+    pub path: Option<String>,  // Relative to the cgroup2 mount, e.g. "/system.slice/foo.service"
+    pub memory_max: Option<u64>,
+    pub memory_current: Option<u64>,
+    pub swap_max: Option<u64>,
+    pub swap_current: Option<u64>,
+    pub cpu_limit: Option<f64>,  // Cores, from cpu.max quota / period
+    pub cpu_usage: Option<f64>,  // Percent of cpu_limit
+    pub pids_max: Option<u64>,
+    pub pids_current: Option<u64>,
+}
+
+/// The parts of memory.stat used to break down a limited cgroup's memory (bytes)
+#[derive(Clone, Copy, Default)]
+pub struct CgroupMemoryStat {
+    pub anon: u64,
+    pub file: u64,
+    pub shmem: u64,
+    pub inactive_file: u64,
+    pub slab_reclaimable: u64,
+    pub slab_unreclaimable: u64,
+    pub kernel_stack: u64,
+    pub pagetables: u64,
+    pub file_dirty: u64,
+    pub file_writeback: u64,
+}
+
+impl CgroupData {
+    pub fn update(&mut self) {
+        if !self.detected {
+            self.detected = true;
+            self.detect();
+        }
+
+        #[cfg(target_os = "linux")]
+        self.update_linux();
+    }
+
+    /// Whether any resource limit is set
+    pub fn is_limited(&self) -> bool {
+        self.memory_max.is_some() || self.cpu_limit.is_some() || self.pids_max.is_some()
+    }
+
+    fn detect(&mut self) {
+        // Markers left by Docker and Podman, and the variable systemd-nspawn,
+        // LXC and Podman set for PID 1
+        self.in_container = ["/.dockerenv", "/run/.containerenv"]
+            .iter()
+            .any(|marker| std::path::Path::new(marker).exists())
+            || std::env::var("container").is_ok_and(|v| !v.is_empty())
+            || std::env::var_os("KUBERNETES_SERVICE_HOST").is_some();
+
+        #[cfg(target_os = "linux")]
+        {
+            let mount = std::fs::read_to_string("/proc/self/mountinfo")
+                .ok()
+                .and_then(|content| find_cgroup2_mount(&content));
+            let path = std::fs::read_to_string("/proc/self/cgroup")
+                .ok()
+                .and_then(|content| parse_unified_cgroup(&content));
+
+            if let (Some(mount), Some(path)) = (mount, path) {
+                self.dir = Some(mount.join(path.trim_start_matches('/')));
+                self.path = Some(path);
+            }
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn update_linux(&mut self) {
+        let Some(dir) = self.dir.clone() else {
+            return;
+        };
+
+        // Walk up to the mount point, since a parent's limit applies as well.
+        // Usage is read from the cgroup that sets each limit, as that's what
+        // the limit is enforced against.
+        let mut memory_max: Option<(u64, &Path)> = None;
+        let mut swap_max: Option<(u64, &Path)> = None;
+        let mut cpu_limit: Option<(f64, &Path)> = None;
+        let mut pids_max: Option<(u64, &Path)> = None;
+        let levels = self.path.as_deref().map_or(0, depth) + 1;
+        for ancestor in dir.ancestors().take(levels) {
+            tighten(&mut memory_max, read_limit(&ancestor.join("memory.max")), ancestor);
+            tighten(&mut swap_max, read_limit(&ancestor.join("memory.swap.max")), ancestor);
+            tighten(&mut pids_max, read_limit(&ancestor.join("pids.max")), ancestor);
+            tighten(&mut cpu_limit, read_cpu_max(&ancestor.join("cpu.max")), ancestor);
+        }
+
+        // Without a limit, report our own cgroup's usage
+        let memory_dir = memory_max.map_or(dir.as_path(), |(_, path)| path);
+        let swap_dir = swap_max.map_or(dir.as_path(), |(_, path)| path);
+        let pids_dir = pids_max.map_or(dir.as_path(), |(_, path)| path);
+        let cpu_dir = cpu_limit.map_or(dir.as_path(), |(_, path)| path);
+
+        self.memory_max = memory_max.map(|(max, _)| max);
+        self.swap_max = swap_max.map(|(max, _)| max);
+        self.cpu_limit = cpu_limit.map(|(cores, _)| cores);
+        self.pids_max = pids_max.map(|(max, _)| max);
+        self.memory_current = read_number(&memory_dir.join("memory.current"));
+        self.swap_current = read_number(&swap_dir.join("memory.swap.current"));
+        self.pids_current = read_number(&pids_dir.join("pids.current"));
+        self.memory_stat = std::fs::read_to_string(memory_dir.join("memory.stat"))
+            .ok()
+            .map(|content| parse_memory_stat(&content));
+
+        // CPU time used since the last update, against what the quota allows
+        let now = Instant::now();
+        let usage_usec = std::fs::read_to_string(cpu_dir.join("cpu.stat"))
+            .ok()
+            .and_then(|content| parse_cpu_usage(&content));
+        self.cpu_usage = match (usage_usec, &self.prev_usage, self.cpu_limit) {
+            // Only compare against a sample from the same cgroup
+            (Some(usage), Some((prev, then, prev_dir)), Some(limit)) if prev_dir == cpu_dir => {
+                let elapsed_usec = now.duration_since(*then).as_micros() as f64;
+                (elapsed_usec > 0.0)
+                    .then(|| (usage.saturating_sub(*prev) as f64 / (elapsed_usec * limit) * 100.0).min(100.0))
+            }
+            _ => None,
+        };
+        self.prev_usage = usage_usec.map(|usage| (usage, now, cpu_dir.to_path_buf()));
+    }
+}
+
+/// Keep the lower of two limits, along with the cgroup that sets it
+#[cfg(target_os = "linux")]
+fn tighten<'a, T: PartialOrd>(current: &mut Option<(T, &'a Path)>, new: Option<T>, dir: &'a Path) {
+    if let Some(new) = new {
+        if !matches!(current, Some((limit, _)) if *limit <= new) {
+            *current = Some((new, dir));
+        }
+    }
+}
+
+/// Number of components in a cgroup path ("/" is 0, "/a/b" is 2)
+#[cfg(target_os = "linux")]
+fn depth(path: &str) -> usize {
+    path.split('/').filter(|part| !part.is_empty()).count()
+}
+
+/// Mount point of the cgroup2 hierarchy (/sys/fs/cgroup, or .../unified on hybrid setups)
+#[cfg(target_os = "linux")]
+fn find_cgroup2_mount(mountinfo: &str) -> Option<PathBuf> {
+    // Format (see proc(5)):
+    //   36 35 98:0 / /sys/fs/cgroup rw,nosuid - cgroup2 cgroup2 rw
+    //   id parent dev root mount_point options [optional...] - fstype source super_options
+    mountinfo.lines().find_map(|line| {
+        let (fields, fs) = line.split_once(" - ")?;
+        if !fs.starts_with("cgroup2 ") {
+            return None;
+        }
+        fields.split_whitespace().nth(4).map(PathBuf::from)
+    })
+}
+
+/// The cgroup from the "0::" (unified hierarchy) line of /proc/<pid>/cgroup
+#[cfg(target_os = "linux")]
+fn parse_unified_cgroup(content: &str) -> Option<String> {
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("0::"))
+        .map(|path| path.trim().to_string())
+}
+
+/// Cgroup a process belongs to, e.g. "/system.slice/sshd.service"
+#[cfg(target_os = "linux")]
+pub fn process_cgroup(pid: u32) -> Option<String> {
+    let content = std::fs::read_to_string(format!("/proc/{}/cgroup", pid)).ok()?;
+    // Hosts without the unified hierarchy still have systemd's named v1 hierarchy,
+    // e.g. "1:name=systemd:/system.slice/sshd.service"
+    parse_unified_cgroup(&content).or_else(|| {
+        content
+            .lines()
+            .find_map(|line| line.split_once(":name=systemd:"))
+            .map(|(_, path)| path.trim().to_string())
+    })
+}
+
+/// Short name for a cgroup: the container runtime and ID for containers,
+/// otherwise the last component (a systemd unit or slice)
+pub fn cgroup_label(path: &str) -> String {
+    let mut components = path.split('/').filter(|part| !part.is_empty()).rev();
+    let Some(leaf) = components.next() else {
+        return "/".to_string();
+    };
+
+    // systemd driver: "docker-<id>.scope"; cgroupfs driver: "/docker/<id>"
+    let unit = leaf.strip_suffix(".scope").unwrap_or(leaf);
+    let runtimes = [
+        ("docker-", "docker"),
+        ("libpod-conmon-", "conmon"),
+        ("libpod-", "podman"),
+        ("cri-containerd-", "containerd"),
+        ("crio-", "cri-o"),
+    ];
+    let container = runtimes
+        .iter()
+        .find_map(|(prefix, runtime)| unit.strip_prefix(prefix).map(|id| (*runtime, id)))
+        .or_else(|| match components.next() {
+            Some("docker") => Some(("docker", leaf)),
+            _ => None,
+        });
+
+    match container {
+        Some((runtime, id)) if id.len() >= 12 && id.chars().all(|c| c.is_ascii_hexdigit()) => {
+            format!("{} {}", runtime, &id[..12])
+        }
+        _ => leaf.to_string(),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read_number(path: &Path) -> Option<u64> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// A single-value limit file, where "max" means no limit
+#[cfg(target_os = "linux")]
+fn read_limit(path: &Path) -> Option<u64> {
+    let content = std::fs::read_to_string(path).ok()?;
+    match content.trim() {
+        "max" => None,
+        value => value.parse().ok(),
+    }
+}
+
+/// cpu.max as cores: "50000 100000" is half a core, "max 100000" is unlimited
+#[cfg(target_os = "linux")]
+fn read_cpu_max(path: &Path) -> Option<f64> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let mut parts = content.split_whitespace();
+    let quota: f64 = parts.next()?.parse().ok()?;
+    let period: f64 = parts.next()?.parse().ok()?;
+    (period > 0.0).then(|| quota / period)
+}
+
+#[cfg(target_os = "linux")]
+fn parse_cpu_usage(content: &str) -> Option<u64> {
+    // Format: "usage_usec 123456\nuser_usec ...\nsystem_usec ..."
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("usage_usec "))
+        .and_then(|value| value.trim().parse().ok())
+}
+
+#[cfg(target_os = "linux")]
+fn parse_memory_stat(content: &str) -> CgroupMemoryStat {
+    // Format: one "key value" pair per line, values in bytes
+    let mut stat = CgroupMemoryStat::default();
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(' ') else {
+            continue;
+        };
+        let value = value.trim().parse().unwrap_or(0);
+        match key {
+            "anon" => stat.anon = value,
+            "file" => stat.file = value,
+            "shmem" => stat.shmem = value,
+            "inactive_file" => stat.inactive_file = value,
+            "slab_reclaimable" => stat.slab_reclaimable = value,
+            "slab_unreclaimable" => stat.slab_unreclaimable = value,
+            "kernel_stack" => stat.kernel_stack = value,
+            "pagetables" => stat.pagetables = value,
+            "file_dirty" => stat.file_dirty = value,
+            "file_writeback" => stat.file_writeback = value,
+            _ => {}
+        }
+    }
+    stat
+}
+
@@ -1,14 +1,23 @@
+use serde::{Deserialize, Serialize};
+#[cfg(target_os = "linux")]
+use std::collections::HashMap;
+#[cfg(target_os = "linux")]
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+#[cfg(target_os = "macos")]
 use std::process::Command;
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ConnectionInfo {
     pub protocol: String,      // TCP, UDP
     pub local_addr: String,    // local address:port
     pub remote_addr: String,   // remote address:port (or * for listening)
     pub state: String,         // LISTEN, ESTABLISHED, etc.
+    pub inode: u64,            // socket inode (0 if unknown)
+    pub pid: Option<u32>,      // owning process, if it could be resolved
+    pub process_name: Option<String>,
 }
 
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct ConnectionData {
     pub connections: Vec<ConnectionInfo>,
     pub listening_ports: Vec<ConnectionInfo>,
@@ -71,6 +80,9 @@ impl ConnectionData {
                     local_addr: format_macos_addr(&local_addr),
                     remote_addr: format_macos_addr(&remote_addr),
                     state: state.clone(),
+                    inode: 0,
+                    pid: None,
+                    process_name: None,
                 };
 
                 if state == "LISTEN" || remote_addr == "*.*" {
@@ -84,44 +96,187 @@ impl ConnectionData {
 
     #[cfg(target_os = "linux")]
     fn update_linux(&mut self) {
-        // Use ss command on Linux (faster than netstat)
-        if let Ok(output) = Command::new("ss")
-            .args(["-tunapH"])
-            .output()
-        {
-            if let Ok(stdout) = String::from_utf8(output.stdout) {
-                self.parse_ss_output(&stdout);
+        // Read the kernel socket tables directly instead of spawning `ss`,
+        // which may not be installed (e.g. minimal containers)
+        let tables = [
+            ("/proc/net/tcp", "TCP"),
+            ("/proc/net/tcp6", "TCP"),
+            ("/proc/net/udp", "UDP"),
+            ("/proc/net/udp6", "UDP"),
+        ];
+
+        // Sockets are matched to processes through their inode numbers
+        let owners = socket_owners();
+
+        for (path, protocol) in tables {
+            if let Ok(content) = std::fs::read_to_string(path) {
+                for mut conn in parse_proc_net(&content, protocol) {
+                    if let Some((pid, name)) = owners.get(&conn.inode) {
+                        conn.pid = Some(*pid);
+                        conn.process_name = Some(name.clone());
+                    }
+
+                    match conn.state.as_str() {
+                        "LISTEN" | "UNCONN" => self.listening_ports.push(conn),
+                        "ESTABLISHED" | "CLOSE_WAIT" | "TIME_WAIT" => self.connections.push(conn),
+                        _ => {}
+                    }
+                }
             }
         }
     }
+}
 
-    #[cfg(target_os = "linux")]
-    fn parse_ss_output(&mut self, output: &str) {
-        // ss -tunapH format:
-        // tcp   LISTEN     0      128     0.0.0.0:22     0.0.0.0:*     users:(("sshd",pid=1234,fd=3))
+/// Parse the contents of a /proc/net/{tcp,tcp6,udp,udp6} table
+#[cfg(target_os = "linux")]
+fn parse_proc_net(content: &str, protocol: &str) -> Vec<ConnectionInfo> {
+    // Format (header line followed by one socket per line):
+    //   sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+    //    0: 0100007F:0277 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 12345
 
-        for line in output.lines() {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() >= 5 {
-                let protocol = parts[0].to_uppercase();
-                let state = parts[1].to_string();
-                let local_addr = parts[4].to_string();
-                let remote_addr = if parts.len() > 5 { parts[5].to_string() } else { "*:*".to_string() };
+    let mut result = Vec::new();
 
-                let conn = ConnectionInfo {
-                    protocol,
-                    local_addr,
-                    remote_addr: remote_addr.clone(),
-                    state: state.clone(),
-                };
+    for line in content.lines().skip(1) {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 4 {
+            continue;
+        }
 
-                if state == "LISTEN" {
-                    self.listening_ports.push(conn);
-                } else if state == "ESTAB" || state == "CLOSE-WAIT" || state == "TIME-WAIT" {
-                    self.connections.push(conn);
-                }
+        let (Some(local), Some(remote)) = (decode_proc_addr(parts[1]), decode_proc_addr(parts[2])) else {
+            continue;
+        };
+        let Ok(state_code) = u8::from_str_radix(parts[3], 16) else {
+            continue;
+        };
+        let inode = parts.get(9).and_then(|s| s.parse::<u64>().ok()).unwrap_or(0);
+
+        // Unconnected UDP sockets report TCP_CLOSE; show them like `ss` does
+        let state = if protocol == "UDP" && state_code == 0x07 {
+            "UNCONN"
+        } else {
+            tcp_state_name(state_code)
+        };
+
+        result.push(ConnectionInfo {
+            protocol: protocol.to_string(),
+            local_addr: format_endpoint(&local),
+            remote_addr: format_endpoint(&remote),
+            state: state.to_string(),
+            inode,
+            pid: None,
+            process_name: None,
+        });
+    }
+
+    result
+}
+
+/// Build a map of socket inode -> (pid, process name) from /proc/<pid>/fd
+#[cfg(target_os = "linux")]
+fn socket_owners() -> HashMap<u64, (u32, String)> {
+    let mut owners = HashMap::new();
+
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return owners;
+    };
+
+    for entry in entries.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|s| s.parse::<u32>().ok()) else {
+            continue;
+        };
+
+        // Other users' fd directories are unreadable without privileges
+        let Ok(fds) = std::fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+
+        let mut name: Option<String> = None;
+        for fd in fds.flatten() {
+            let Some(inode) = std::fs::read_link(fd.path())
+                .ok()
+                .and_then(|target| parse_socket_link(&target.to_string_lossy()))
+            else {
+                continue;
+            };
+
+            let name = name.get_or_insert_with(|| {
+                std::fs::read_to_string(entry.path().join("comm"))
+                    .map(|s| s.trim().to_string())
+                    .unwrap_or_default()
+            });
+
+            // Sockets shared across fork() are attributed to the first owner found
+            owners.entry(inode).or_insert_with(|| (pid, name.clone()));
+        }
+    }
+
+    owners
+}
+
+/// Extract the inode from an fd link target like "socket:[12345]"
+#[cfg(target_os = "linux")]
+fn parse_socket_link(target: &str) -> Option<u64> {
+    target
+        .strip_prefix("socket:[")?
+        .strip_suffix(']')?
+        .parse()
+        .ok()
+}
+
+/// Decode a hex "ADDR:PORT" pair as printed by the kernel
+#[cfg(target_os = "linux")]
+fn decode_proc_addr(field: &str) -> Option<SocketAddr> {
+    // The address is printed as native-endian 32-bit words (one for IPv4, four
+    // for IPv6), while the port is printed as a plain big-endian number
+    let (addr_hex, port_hex) = field.split_once(':')?;
+    let port = u16::from_str_radix(port_hex, 16).ok()?;
+
+    let ip = match addr_hex.len() {
+        8 => {
+            let word = u32::from_str_radix(addr_hex, 16).ok()?;
+            IpAddr::V4(Ipv4Addr::from(word.to_ne_bytes()))
+        }
+        32 => {
+            let mut octets = [0u8; 16];
+            for (i, chunk) in octets.chunks_mut(4).enumerate() {
+                let word = u32::from_str_radix(&addr_hex[i * 8..i * 8 + 8], 16).ok()?;
+                chunk.copy_from_slice(&word.to_ne_bytes());
             }
+            IpAddr::V6(Ipv6Addr::from(octets))
         }
+        _ => return None,
+    };
+
+    Some(SocketAddr::new(ip, port))
+}
+
+/// Format an endpoint like `ss`, using `*` for the wildcard port
+#[cfg(target_os = "linux")]
+fn format_endpoint(addr: &SocketAddr) -> String {
+    match (addr.ip(), addr.port()) {
+        (IpAddr::V4(ip), 0) => format!("{}:*", ip),
+        (IpAddr::V6(ip), 0) => format!("[{}]:*", ip),
+        _ => addr.to_string(),
+    }
+}
+
+/// Map a kernel TCP state code (include/net/tcp_states.h) to its name
+#[cfg(target_os = "linux")]
+fn tcp_state_name(code: u8) -> &'static str {
+    match code {
+        0x01 => "ESTABLISHED",
+        0x02 => "SYN_SENT",
+        0x03 => "SYN_RECV",
+        0x04 => "FIN_WAIT1",
+        0x05 => "FIN_WAIT2",
+        0x06 => "TIME_WAIT",
+        0x07 => "CLOSE",
+        0x08 => "CLOSE_WAIT",
+        0x09 => "LAST_ACK",
+        0x0A => "LISTEN",
+        0x0B => "CLOSING",
+        0x0C => "NEW_SYN_RECV",
+        _ => "UNKNOWN",
     }
 }
 
@@ -141,3 +296,119 @@ fn format_macos_addr(addr: &str) -> String {
     }
 }
 
+
+// Fixtures are as printed on little-endian machines, where the address words
+// appear byte-swapped
+#[cfg(all(test, target_os = "linux", target_endian = "little"))]
+mod tests {
+    use super::*;
+
+    const TCP: &str = "\
+  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:0277 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 21431 1 0000000000000000 100 0 0 10 0
+   1: 0F02000A:D2A4 2210D9AC:01BB 01 00000000:00000000 02:000A8F5B 00000000  1000        0 88123 2 0000000000000000 20 4 30 10 -1
+";
+
+    const TCP6: &str = "\
+  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000000000000000000001000000:0CEA 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000   112        0 30211 1 0000000000000000 100 0 0 10 0
+   1: B80D0120000000000000000001000000:01BB B80D0120000000000000000002000000:C350 01 00000000:00000000 00:00000000 00000000     0        0 30555 1 0000000000000000 20 4 30 10 -1
+";
+
+    const UDP: &str = "\
+  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops
+  123: 3500007F:0035 00000000:0000 07 00000000:00000000 00:00000000 00000000   101        0 19000 2 0000000000000000 0
+  456: 0F02000A:9C40 08080808:0035 01 00000000:00000000 00:00000000 00000000  1000        0 19555 2 0000000000000000 0
+";
+
+    #[test]
+    fn parses_ipv4_tcp() {
+        let conns = parse_proc_net(TCP, "TCP");
+        assert_eq!(conns.len(), 2);
+
+        assert_eq!(conns[0].protocol, "TCP");
+        assert_eq!(conns[0].local_addr, "127.0.0.1:631");
+        assert_eq!(conns[0].remote_addr, "0.0.0.0:*");
+        assert_eq!(conns[0].state, "LISTEN");
+        assert_eq!(conns[0].inode, 21431);
+
+        assert_eq!(conns[1].local_addr, "10.0.2.15:53924");
+        assert_eq!(conns[1].remote_addr, "172.217.16.34:443");
+        assert_eq!(conns[1].state, "ESTABLISHED");
+        assert_eq!(conns[1].inode, 88123);
+    }
+
+    #[test]
+    fn parses_ipv6_tcp() {
+        let conns = parse_proc_net(TCP6, "TCP");
+        assert_eq!(conns.len(), 2);
+
+        assert_eq!(conns[0].local_addr, "[::1]:3306");
+        assert_eq!(conns[0].remote_addr, "[::]:*");
+        assert_eq!(conns[0].state, "LISTEN");
+
+        // Each 32-bit word is byte-swapped, but the words stay in order
+        assert_eq!(conns[1].local_addr, "[2001:db8::1]:443");
+        assert_eq!(conns[1].remote_addr, "[2001:db8::2]:50000");
+        assert_eq!(conns[1].state, "ESTABLISHED");
+        assert_eq!(conns[1].inode, 30555);
+    }
+
+    #[test]
+    fn decodes_addresses() {
+        let addr = decode_proc_addr("0100007F:1F90").unwrap();
+        assert_eq!(addr, "127.0.0.1:8080".parse().unwrap());
+
+        let addr = decode_proc_addr("0000000000000000FFFF00000100007F:0050").unwrap();
+        assert_eq!(addr, "[::ffff:127.0.0.1]:80".parse().unwrap());
+
+        assert!(decode_proc_addr("0100007F").is_none());
+        assert!(decode_proc_addr("7F:0050").is_none());
+        assert!(decode_proc_addr("ZZZZZZZZ:0050").is_none());
+    }
+
+    #[test]
+    fn names_every_tcp_state() {
+        let names = [
+            "ESTABLISHED", "SYN_SENT", "SYN_RECV", "FIN_WAIT1", "FIN_WAIT2", "TIME_WAIT",
+            "CLOSE", "CLOSE_WAIT", "LAST_ACK", "LISTEN", "CLOSING", "NEW_SYN_RECV",
+        ];
+
+        let mut table = String::from("  sl  local_address rem_address   st\n");
+        for code in 1..=12 {
+            table.push_str(&format!("   {}: 0100007F:1F90 0100007F:C350 {:02X} 00000000:00000000 00:00000000 00000000     0        0 {}\n", code, code, 1000 + code));
+        }
+
+        let states: Vec<String> = parse_proc_net(&table, "TCP").into_iter().map(|c| c.state).collect();
+        assert_eq!(states, names);
+
+        for (code, name) in (1..=12).zip(names) {
+            assert_eq!(tcp_state_name(code), name);
+        }
+        assert_eq!(tcp_state_name(0x00), "UNKNOWN");
+        assert_eq!(tcp_state_name(0x0D), "UNKNOWN");
+    }
+
+    #[test]
+    fn parses_udp_states() {
+        let conns = parse_proc_net(UDP, "UDP");
+        assert_eq!(conns.len(), 2);
+
+        // Unconnected sockets report TCP_CLOSE, shown as UNCONN
+        assert_eq!(conns[0].protocol, "UDP");
+        assert_eq!(conns[0].local_addr, "127.0.0.53:53");
+        assert_eq!(conns[0].remote_addr, "0.0.0.0:*");
+        assert_eq!(conns[0].state, "UNCONN");
+        assert_eq!(conns[0].inode, 19000);
+
+        assert_eq!(conns[1].local_addr, "10.0.2.15:40000");
+        assert_eq!(conns[1].remote_addr, "8.8.8.8:53");
+        assert_eq!(conns[1].state, "ESTABLISHED");
+    }
+
+    #[test]
+    fn skips_malformed_lines() {
+        let table = "header\n   0: garbage\n   1: 0100007F:0277 00000000:0000 XX\n";
+        assert!(parse_proc_net(table, "TCP").is_empty());
+    }
+}
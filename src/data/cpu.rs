@@ -1,7 +1,12 @@
+use super::CgroupData;
+use serde::{Deserialize, Serialize};
 use sysinfo::System;
 
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct CpuData {
+    #[cfg(target_os = "linux")]
+    #[serde(skip)]
+    prev_stat: Option<[u64; 8]>,
     pub total_usage: f64,
     pub per_core_usage: Vec<f64>,
     pub per_core_freq: Vec<u64>,
@@ -10,6 +15,39 @@ pub struct CpuData {
     pub frequency: u64,
     pub frequency_min: u64,
     pub frequency_max: u64,
+    // Split of CPU time by state; None until two samples exist or off Linux
+    #[serde(default)]
+    pub times: Option<CpuTimes>,
+    // Cores the cgroup may use; total_usage is then relative to this
+    #[serde(default)]
+    pub limit: Option<f64>,
+}
+
+/// Share of total CPU time spent in each state over the last interval, in percent
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct CpuTimes {
+    pub user: f64,
+    pub nice: f64,
+    pub system: f64,
+    pub irq: f64,
+    pub softirq: f64,
+    pub iowait: f64,
+    pub steal: f64,
+}
+
+impl CpuTimes {
+    /// Every busy state by name, in stacking order (bottom first)
+    pub fn parts(&self) -> [(&'static str, f64); 7] {
+        [
+            ("user", self.user),
+            ("nice", self.nice),
+            ("system", self.system),
+            ("irq", self.irq),
+            ("softirq", self.softirq),
+            ("iowait", self.iowait),
+            ("steal", self.steal),
+        ]
+    }
 }
 
 impl CpuData {
@@ -36,6 +74,62 @@ impl CpuData {
             self.frequency_min = *self.per_core_freq.iter().min().unwrap_or(&0);
             self.frequency_max = *self.per_core_freq.iter().max().unwrap_or(&0);
         }
+
+        #[cfg(target_os = "linux")]
+        self.update_times();
+    }
+
+    /// Report usage against the cgroup's CPU quota rather than the whole host
+    pub fn apply_cgroup(&mut self, cgroup: &CgroupData) {
+        self.limit = cgroup.cpu_limit.filter(|&cores| cores < self.core_count as f64);
+        if self.limit.is_some() {
+            // Nothing to compare against until the second sample
+            self.total_usage = cgroup.cpu_usage.unwrap_or(0.0);
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn update_times(&mut self) {
+        let Some(current) = std::fs::read_to_string("/proc/stat")
+            .ok()
+            .and_then(|content| parse_stat(&content))
+        else {
+            self.times = None;
+            return;
+        };
+
+        if let Some(prev) = self.prev_stat {
+            let delta: Vec<f64> = current
+                .iter()
+                .zip(prev.iter())
+                .map(|(c, p)| c.saturating_sub(*p) as f64)
+                .collect();
+            let total: f64 = delta.iter().sum();
+
+            // Counters only move in whole ticks, so a very short interval can be empty
+            if total > 0.0 {
+                let percent = |i: usize| delta[i] / total * 100.0;
+                self.times = Some(CpuTimes {
+                    user: percent(0),
+                    nice: percent(1),
+                    system: percent(2),
+                    iowait: percent(4),
+                    irq: percent(5),
+                    softirq: percent(6),
+                    steal: percent(7),
+                });
+            }
+        }
+
+        self.prev_stat = Some(current);
+    }
+
+    /// "8c", or "0.5 of 8c" under a cgroup CPU quota
+    pub fn format_cores(&self) -> String {
+        match self.limit {
+            Some(cores) => format!("{:.1} of {}c", cores, self.core_count),
+            None => format!("{}c", self.core_count),
+        }
     }
 
     pub fn format_frequency(freq_mhz: u64) -> String {
@@ -46,3 +140,26 @@ impl CpuData {
         }
     }
 }
+
+/// Parse the aggregate "cpu" line of /proc/stat
+#[cfg(target_os = "linux")]
+fn parse_stat(content: &str) -> Option<[u64; 8]> {
+    // Format (in clock ticks since boot):
+    //   cpu  user nice system idle iowait irq softirq steal guest guest_nice
+    //   cpu  4705 356 584 3699176 23060 0 277 0 0 0
+    // guest and guest_nice are already counted in user and nice
+
+    let line = content.lines().find(|line| line.starts_with("cpu "))?;
+    let mut fields = [0u64; 8];
+    let mut values = line.split_whitespace().skip(1);
+
+    // Older kernels lack the later fields; those stay at zero
+    for field in fields.iter_mut() {
+        match values.next() {
+            Some(value) => *field = value.parse().ok()?,
+            None => break,
+        }
+    }
+
+    Some(fields)
+}
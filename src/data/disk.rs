@@ -1,10 +1,27 @@
+#[cfg(target_os = "linux")]
+use super::rate::per_second;
+#[cfg(target_os = "linux")]
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
 use sysinfo::Disks;
 
+#[derive(Serialize, Deserialize)]
 pub struct DiskData {
+    #[serde(skip)]
     disks: Disks,
+    #[cfg(target_os = "linux")]
+    #[serde(skip)]
+    prev_stats: HashMap<String, DiskStats>,
+    #[serde(skip)]
+    last_update: Option<Instant>,
     pub disk_info: Vec<DiskInfo>,
+    pub io_devices: Vec<BlockDeviceInfo>,
+    pub read_rate: u64,   // bytes/s across all physical devices
+    pub write_rate: u64,  // bytes/s across all physical devices
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct DiskInfo {
     pub mount_point: String,
     pub total: u64,
@@ -12,11 +29,42 @@ pub struct DiskInfo {
     pub used_percent: f64,
 }
 
+/// I/O activity of a whole block device (partitions are not included)
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BlockDeviceInfo {
+    pub name: String,
+    pub read_rate: u64,    // bytes/s
+    pub write_rate: u64,   // bytes/s
+    pub read_iops: u64,
+    pub write_iops: u64,
+    pub busy_percent: f64, // share of wall time with I/O in flight
+    // Built on other devices (device-mapper, md RAID), whose I/O is counted there too
+    #[serde(default)]
+    pub stacked: bool,
+}
+
+/// Cumulative counters from one /proc/diskstats line
+#[cfg(target_os = "linux")]
+#[derive(Clone, Copy, Default)]
+struct DiskStats {
+    reads: u64,
+    sectors_read: u64,
+    writes: u64,
+    sectors_written: u64,
+    io_ticks_ms: u64,
+}
+
 impl Default for DiskData {
     fn default() -> Self {
         Self {
             disks: Disks::new_with_refreshed_list(),
+            #[cfg(target_os = "linux")]
+            prev_stats: HashMap::new(),
+            last_update: None,
             disk_info: Vec::new(),
+            io_devices: Vec::new(),
+            read_rate: 0,
+            write_rate: 0,
         }
     }
 }
@@ -46,6 +94,67 @@ impl DiskData {
 
         // Sort by mount point for consistent ordering
         self.disk_info.sort_by(|a, b| a.mount_point.cmp(&b.mount_point));
+
+        // Rates use the measured time since the previous sample
+        let now = Instant::now();
+        let elapsed = self
+            .last_update
+            .map(|t| now.duration_since(t))
+            .unwrap_or(Duration::ZERO);
+        self.last_update = Some(now);
+
+        self.io_devices.clear();
+
+        #[cfg(target_os = "linux")]
+        self.update_linux_io(elapsed);
+
+        #[cfg(not(target_os = "linux"))]
+        let _ = elapsed;
+
+        (self.read_rate, self.write_rate) = physical_totals(&self.io_devices);
+    }
+
+    #[cfg(target_os = "linux")]
+    fn update_linux_io(&mut self, elapsed: Duration) {
+        let Ok(content) = std::fs::read_to_string("/proc/diskstats") else {
+            return;
+        };
+
+        let stats = parse_diskstats(&content, &|name| {
+            std::path::Path::new("/sys/block").join(name).exists()
+        });
+
+        for (name, current) in &stats {
+            let prev = self.prev_stats.get(name).copied().unwrap_or(*current);
+            let busy_ms = current.io_ticks_ms.saturating_sub(prev.io_ticks_ms) as f64;
+            let busy_percent = if elapsed.is_zero() {
+                0.0
+            } else {
+                (busy_ms / elapsed.as_millis() as f64 * 100.0).min(100.0)
+            };
+
+            self.io_devices.push(BlockDeviceInfo {
+                name: name.clone(),
+                read_rate: per_second(prev.sectors_read, current.sectors_read, elapsed) * SECTOR_SIZE,
+                write_rate: per_second(prev.sectors_written, current.sectors_written, elapsed) * SECTOR_SIZE,
+                read_iops: per_second(prev.reads, current.reads, elapsed),
+                write_iops: per_second(prev.writes, current.writes, elapsed),
+                busy_percent,
+                stacked: is_stacked(name),
+            });
+        }
+
+        self.io_devices.sort_by(|a, b| a.name.cmp(&b.name));
+        self.prev_stats = stats;
+    }
+
+    /// The device with the highest busy percentage, if any
+    pub fn busiest_device(&self) -> Option<&BlockDeviceInfo> {
+        self.io_devices.iter().max_by(|a, b| {
+            a.busy_percent
+                .partial_cmp(&b.busy_percent)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
     }
 
     pub fn format_bytes(bytes: u64) -> String {
@@ -67,3 +176,121 @@ impl DiskData {
         }
     }
 }
+
+// /proc/diskstats always counts in 512-byte sectors, regardless of the device
+#[cfg(target_os = "linux")]
+const SECTOR_SIZE: u64 = 512;
+
+/// Read and write rates summed over devices that aren't built on others,
+/// so I/O through LVM, LUKS or RAID isn't counted twice
+fn physical_totals(devices: &[BlockDeviceInfo]) -> (u64, u64) {
+    devices
+        .iter()
+        .filter(|d| !d.stacked)
+        .fold((0, 0), |(read, write), d| (read + d.read_rate, write + d.write_rate))
+}
+
+/// Whether a block device sits on top of others (listed in its slaves directory)
+#[cfg(target_os = "linux")]
+fn is_stacked(name: &str) -> bool {
+    std::fs::read_dir(std::path::Path::new("/sys/block").join(name).join("slaves"))
+        .is_ok_and(|mut slaves| slaves.next().is_some())
+}
+
+/// Parse /proc/diskstats, keeping only whole block devices (those `is_device` accepts)
+#[cfg(target_os = "linux")]
+fn parse_diskstats(content: &str, is_device: &dyn Fn(&str) -> bool) -> HashMap<String, DiskStats> {
+    // Format:
+    //   major minor name reads reads_merged sectors_read ms_reading
+    //   writes writes_merged sectors_written ms_writing in_flight io_ticks ...
+    //   8       0 sda 12345 678 901234 5678 2345 678 90123 4567 0 8901 2345
+
+    let mut stats = HashMap::new();
+
+    for line in content.lines() {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 13 {
+            continue;
+        }
+
+        let name = parts[2];
+
+        // Partitions have no /sys/block entry; loop and RAM disks are noise
+        if name.starts_with("loop") || name.starts_with("ram") || name.starts_with("zram") {
+            continue;
+        }
+        if !is_device(name) {
+            continue;
+        }
+
+        let field = |i: usize| parts[i].parse::<u64>().unwrap_or(0);
+
+        stats.insert(
+            name.to_string(),
+            DiskStats {
+                reads: field(3),
+                sectors_read: field(5),
+                writes: field(7),
+                sectors_written: field(9),
+                io_ticks_ms: field(12),
+            },
+        );
+    }
+
+    stats
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    const DISKSTATS: &str = "\
+   7       0 loop0 120 0 2400 30 0 0 0 0 0 40 30 0 0 0 0
+   8       0 sda 5000 100 400000 2000 3000 50 240000 1500 0 2600 3500 0 0 0 0
+   8       1 sda1 4900 100 390000 1900 2900 50 230000 1400 0 2500 3300 0 0 0 0
+ 259       0 nvme0n1 8000 0 640000 900 6000 0 480000 700 1 1200 1600 0 0 0 0
+ 259       1 nvme0n1p1 7900 0 630000 880 5900 0 470000 690 0 1150 1570 0 0 0 0
+ 253       0 dm-0 7800 0 620000 950 5800 0 460000 750 0 1180 1700 0 0 0 0
+   8      16 sdb 12 0
+";
+
+    #[test]
+    fn keeps_whole_devices() {
+        // What /sys/block would have: no partitions
+        let is_device = |name: &str| ["loop0", "sda", "sdb", "nvme0n1", "dm-0"].contains(&name);
+        let stats = parse_diskstats(DISKSTATS, &is_device);
+
+        let mut names: Vec<&str> = stats.keys().map(|s| s.as_str()).collect();
+        names.sort();
+        // Loop devices are skipped, and sdb's line is truncated
+        assert_eq!(names, ["dm-0", "nvme0n1", "sda"]);
+
+        let sda = stats["sda"];
+        assert_eq!(sda.reads, 5000);
+        assert_eq!(sda.sectors_read, 400000);
+        assert_eq!(sda.writes, 3000);
+        assert_eq!(sda.sectors_written, 240000);
+        assert_eq!(sda.io_ticks_ms, 2600);
+    }
+
+    #[test]
+    fn totals_skip_stacked_devices() {
+        let device = |name: &str, read_rate: u64, write_rate: u64, stacked: bool| BlockDeviceInfo {
+            name: name.to_string(),
+            read_rate,
+            write_rate,
+            read_iops: 0,
+            write_iops: 0,
+            busy_percent: 0.0,
+            stacked,
+        };
+
+        // dm-0 (LUKS on nvme0n1p1) sees the same I/O as nvme0n1
+        let devices = [
+            device("dm-0", 1000, 400, true),
+            device("nvme0n1", 1000, 400, false),
+            device("sda", 50, 10, false),
+        ];
+        assert_eq!(physical_totals(&devices), (1050, 410));
+    }
+}
@@ -1,6 +1,8 @@
+use super::CgroupData;
+use serde::{Deserialize, Serialize};
 use sysinfo::System;
 
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct MemoryData {
     pub total: u64,
     pub used: u64,
@@ -13,6 +15,56 @@ pub struct MemoryData {
     pub swap_percent: f64,
     // Additional breakdown (where available)
     pub cached: u64,
+    // Full /proc/meminfo breakdown, Linux only
+    #[serde(default)]
+    pub details: Option<MemoryDetails>,
+    // Totals are the cgroup's memory.max rather than the host's RAM
+    #[serde(default)]
+    pub cgroup_limited: bool,
+}
+
+/// Where memory goes, from /proc/meminfo (all in bytes)
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct MemoryDetails {
+    pub anon: u64,                // Process memory not backed by files
+    pub buffers: u64,
+    pub shmem: u64,               // tmpfs and shared memory, counted in Cached
+    pub slab_reclaimable: u64,
+    pub slab_unreclaimable: u64,
+    pub kernel_stack: u64,
+    pub page_tables: u64,
+    pub dirty: u64,               // Waiting to be written back
+    pub writeback: u64,           // Being written back right now
+    pub hugepages_total: u64,
+    pub hugepages_free: u64,
+}
+
+/// What counts as "used" memory
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UsedMemory {
+    /// Total minus MemAvailable, the kernel's estimate of what could be freed;
+    /// this is also what sysinfo reports as used on Linux
+    #[default]
+    Available,
+    /// Total minus free, buffers, page cache and reclaimable slab, as `free` reports it
+    /// (sysinfo's used figure when /proc/meminfo isn't available)
+    ExcludingCache,
+}
+
+impl UsedMemory {
+    pub fn next(self) -> Self {
+        match self {
+            UsedMemory::Available => UsedMemory::ExcludingCache,
+            UsedMemory::ExcludingCache => UsedMemory::Available,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            UsedMemory::Available => "total - available",
+            UsedMemory::ExcludingCache => "total - free - cache",
+        }
+    }
 }
 
 impl MemoryData {
@@ -51,16 +103,105 @@ impl MemoryData {
 
     #[cfg(target_os = "linux")]
     fn update_linux_details(&mut self) {
-        if let Ok(content) = std::fs::read_to_string("/proc/meminfo") {
-            for line in content.lines() {
-                if line.starts_with("Cached:") {
-                    self.cached = parse_meminfo_value(line);
-                    break;
-                }
+        let Ok(content) = std::fs::read_to_string("/proc/meminfo") else {
+            self.details = None;
+            return;
+        };
+
+        let mut details = MemoryDetails::default();
+        let mut hugepages = (0, 0);
+        let mut hugepage_size = 0;
+
+        for line in content.lines() {
+            let Some((key, _)) = line.split_once(':') else {
+                continue;
+            };
+            let value = parse_meminfo_value(line);
+            match key {
+                "Cached" => self.cached = value,
+                "AnonPages" => details.anon = value,
+                "Buffers" => details.buffers = value,
+                "Shmem" => details.shmem = value,
+                "SReclaimable" => details.slab_reclaimable = value,
+                "SUnreclaim" => details.slab_unreclaimable = value,
+                "KernelStack" => details.kernel_stack = value,
+                "PageTables" => details.page_tables = value,
+                "Dirty" => details.dirty = value,
+                "Writeback" => details.writeback = value,
+                "Hugepagesize" => hugepage_size = value,
+                "HugePages_Total" => hugepages.0 = parse_meminfo_count(line),
+                "HugePages_Free" => hugepages.1 = parse_meminfo_count(line),
+                _ => {}
+            }
+        }
+
+        details.hugepages_total = hugepages.0 * hugepage_size;
+        details.hugepages_free = hugepages.1 * hugepage_size;
+        self.details = Some(details);
+    }
+
+    /// Report memory against the cgroup's limits when they're below the host's
+    pub fn apply_cgroup(&mut self, cgroup: &CgroupData) {
+        self.cgroup_limited = false;
+
+        if let (Some(max), Some(current)) = (cgroup.memory_max, cgroup.memory_current) {
+            if max < self.total {
+                let stat = cgroup.memory_stat.unwrap_or_default();
+                self.cgroup_limited = true;
+                self.total = max;
+                self.free = max.saturating_sub(current);
+                // Inactive page cache is what the kernel reclaims first under the limit
+                self.used = current.saturating_sub(stat.inactive_file);
+                self.available = max.saturating_sub(self.used);
+                self.used_percent = self.used as f64 / max.max(1) as f64 * 100.0;
+                self.cached = stat.file;
+
+                // Break down the cgroup's own usage, not the host's
+                self.details = cgroup.memory_stat.map(|stat| MemoryDetails {
+                    anon: stat.anon,
+                    shmem: stat.shmem,
+                    slab_reclaimable: stat.slab_reclaimable,
+                    slab_unreclaimable: stat.slab_unreclaimable,
+                    kernel_stack: stat.kernel_stack,
+                    page_tables: stat.pagetables,
+                    dirty: stat.file_dirty,
+                    writeback: stat.file_writeback,
+                    ..Default::default()
+                });
+            }
+        }
+
+        if let (Some(max), Some(current)) = (cgroup.swap_max, cgroup.swap_current) {
+            if max < self.swap_total {
+                self.swap_total = max;
+                self.swap_used = current.min(max);
+                self.swap_free = max - self.swap_used;
+                self.swap_percent = self.swap_used as f64 / max.max(1) as f64 * 100.0;
             }
         }
     }
 
+    /// Used bytes and percent of total under the given definition
+    pub fn used_as(&self, definition: UsedMemory) -> (u64, f64) {
+        let used = match (definition, &self.details) {
+            (UsedMemory::Available, _) => self.total.saturating_sub(self.available),
+            (UsedMemory::ExcludingCache, Some(details)) => self
+                .total
+                .saturating_sub(self.free)
+                .saturating_sub(details.buffers)
+                .saturating_sub(self.cached)
+                .saturating_sub(details.slab_reclaimable),
+            (UsedMemory::ExcludingCache, None) => self.used,
+        };
+
+        let percent = if self.total > 0 {
+            used as f64 / self.total as f64 * 100.0
+        } else {
+            0.0
+        };
+        (used, percent)
+    }
+
     pub fn format_bytes(bytes: u64) -> String {
         const KB: f64 = 1024.0;
         const MB: f64 = KB * 1024.0;
@@ -88,3 +229,12 @@ fn parse_meminfo_value(line: &str) -> u64 {
         .map(|kb| kb * 1024) // Convert from kB to bytes
         .unwrap_or(0)
 }
+
+#[cfg(target_os = "linux")]
+fn parse_meminfo_count(line: &str) -> u64 {
+    // Format: "HugePages_Total:       0" (a page count, no unit)
+    line.split_whitespace()
+        .nth(1)
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(0)
+}
@@ -1,17 +1,24 @@
 mod battery;
+mod cgroup;
 mod connection;
 mod cpu;
 mod disk;
 mod memory;
 mod network;
+mod pressure;
 mod process;
+mod process_net;
+mod rate;
 mod temperature;
 
 pub use battery::{BatteryData, BatteryState};
+pub use cgroup::CgroupData;
 pub use connection::ConnectionData;
-pub use cpu::CpuData;
+pub use cpu::{CpuData, CpuTimes};
 pub use disk::DiskData;
-pub use memory::MemoryData;
+pub use memory::{MemoryData, MemoryDetails, UsedMemory};
 pub use network::NetworkData;
-pub use process::{ProcessData, ProcessInfo, SortColumn};
+pub use pressure::{Pressure, PressureData};
+pub use process::{ProcessData, ProcessInfo, ProcessOptions, ProcessView, SortColumn};
+pub use process_net::ProcessNetData;
 pub use temperature::TemperatureData;
@@ -1,10 +1,36 @@
+use serde::{Deserialize, Serialize};
 use sysinfo::{Networks, System};
+use super::rate::per_second;
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
+#[derive(Clone, Serialize, Deserialize)]
+pub struct InterfaceInfo {
+    pub name: String,
+    pub speed_down: u64,
+    pub speed_up: u64,
+    pub total_received: u64,
+    pub total_transmitted: u64,
+    pub errors_rx: u64,
+    pub errors_tx: u64,
+    pub drops_rx: Option<u64>,  // Only available on Linux
+    pub drops_tx: Option<u64>,
+    pub excluded: bool,         // Left out of the totals by config
+}
+
+#[derive(Serialize, Deserialize)]
 pub struct NetworkData {
+    #[serde(skip)]
     networks: Networks,
+    #[serde(skip)]
     prev_received: HashMap<String, u64>,
+    #[serde(skip)]
     prev_transmitted: HashMap<String, u64>,
+    #[serde(skip)]
+    last_update: Option<Instant>,
+    #[serde(skip)]
+    exclude: Vec<String>,
+    pub interfaces: Vec<InterfaceInfo>,
     pub total_received: u64,
     pub total_transmitted: u64,
     pub speed_down: u64,
@@ -17,6 +43,9 @@ impl Default for NetworkData {
             networks: Networks::new_with_refreshed_list(),
             prev_received: HashMap::new(),
             prev_transmitted: HashMap::new(),
+            last_update: None,
+            exclude: Vec::new(),
+            interfaces: Vec::new(),
             total_received: 0,
             total_transmitted: 0,
             speed_down: 0,
@@ -26,8 +55,27 @@ impl Default for NetworkData {
 }
 
 impl NetworkData {
+    /// Set interface name patterns to leave out of the totals (e.g. "lo", "veth*")
+    pub fn set_excluded(&mut self, patterns: &[String]) {
+        self.exclude = patterns.to_vec();
+    }
+
+    pub fn is_excluded(&self, name: &str) -> bool {
+        self.exclude.iter().any(|pattern| matches_pattern(name, pattern))
+    }
+
     pub fn update(&mut self, _system: &System) -> (u64, u64) {
         self.networks.refresh();
+        self.interfaces.clear();
+
+        // Rates use the measured time since the previous sample, so they stay
+        // correct whatever the refresh interval is
+        let now = Instant::now();
+        let elapsed = self
+            .last_update
+            .map(|t| now.duration_since(t))
+            .unwrap_or(Duration::ZERO);
+        self.last_update = Some(now);
 
         let mut total_down: u64 = 0;
         let mut total_up: u64 = 0;
@@ -38,27 +86,74 @@ impl NetworkData {
             let received = data.total_received();
             let transmitted = data.total_transmitted();
 
-            new_total_received += received;
-            new_total_transmitted += transmitted;
-
             let prev_rx = self.prev_received.get(name).copied().unwrap_or(received);
             let prev_tx = self.prev_transmitted.get(name).copied().unwrap_or(transmitted);
 
-            let speed_down = received.saturating_sub(prev_rx);
-            let speed_up = transmitted.saturating_sub(prev_tx);
+            let speed_down = per_second(prev_rx, received, elapsed);
+            let speed_up = per_second(prev_tx, transmitted, elapsed);
 
-            total_down += speed_down;
-            total_up += speed_up;
+            let excluded = self.is_excluded(name);
+            if !excluded {
+                new_total_received += received;
+                new_total_transmitted += transmitted;
+                total_down += speed_down;
+                total_up += speed_up;
+            }
+
+            let (drops_rx, drops_tx) = read_drops(name);
+
+            self.interfaces.push(InterfaceInfo {
+                name: name.clone(),
+                speed_down,
+                speed_up,
+                total_received: received,
+                total_transmitted: transmitted,
+                errors_rx: data.total_errors_on_received(),
+                errors_tx: data.total_errors_on_transmitted(),
+                drops_rx,
+                drops_tx,
+                excluded,
+            });
 
             self.prev_received.insert(name.clone(), received);
             self.prev_transmitted.insert(name.clone(), transmitted);
         }
 
+        // Sort by name for a stable cycling order
+        self.interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+
         self.total_received = new_total_received;
         self.total_transmitted = new_total_transmitted;
-        self.speed_down = total_down * 4; // Multiply by 4 since we update every 250ms
-        self.speed_up = total_up * 4;
+        self.speed_down = total_down;
+        self.speed_up = total_up;
 
         (self.speed_up, self.speed_down)
     }
+
+    pub fn interface(&self, name: &str) -> Option<&InterfaceInfo> {
+        self.interfaces.iter().find(|iface| iface.name == name)
+    }
+}
+
+/// Match an interface name against a pattern, with an optional trailing `*` wildcard
+fn matches_pattern(name: &str, pattern: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => name == pattern,
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read_drops(name: &str) -> (Option<u64>, Option<u64>) {
+    let read = |file: &str| {
+        std::fs::read_to_string(format!("/sys/class/net/{}/statistics/{}", name, file))
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+    };
+    (read("rx_dropped"), read("tx_dropped"))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_drops(_name: &str) -> (Option<u64>, Option<u64>) {
+    (None, None)
 }
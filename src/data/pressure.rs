@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+
+/// Pressure stall information from /proc/pressure (Linux 4.20+ with PSI enabled).
+/// Each resource is None when the kernel doesn't report it.
+#[derive(Default, Serialize, Deserialize)]
+pub struct PressureData {
+    pub cpu: Option<Pressure>,
+    pub memory: Option<Pressure>,
+    pub io: Option<Pressure>,
+}
+
+/// Share of wall time tasks were stalled on one resource
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Pressure {
+    pub some: PressureAvg,          // At least one task stalled
+    pub full: Option<PressureAvg>,  // All non-idle tasks stalled (missing for CPU on older kernels)
+}
+
+/// Stall percentages averaged over 10s, 60s and 300s
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct PressureAvg {
+    pub avg10: f64,
+    pub avg60: f64,
+    pub avg300: f64,
+}
+
+impl PressureData {
+    pub fn update(&mut self) {
+        #[cfg(target_os = "linux")]
+        {
+            let read = |resource: &str| {
+                std::fs::read_to_string(format!("/proc/pressure/{}", resource))
+                    .ok()
+                    .and_then(|content| parse_pressure(&content))
+            };
+            self.cpu = read("cpu");
+            self.memory = read("memory");
+            self.io = read("io");
+        }
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.cpu.is_some() || self.memory.is_some() || self.io.is_some()
+    }
+
+    /// Every resource with its name, in display order
+    pub fn resources(&self) -> [(&'static str, Option<&Pressure>); 3] {
+        [
+            ("cpu", self.cpu.as_ref()),
+            ("memory", self.memory.as_ref()),
+            ("io", self.io.as_ref()),
+        ]
+    }
+}
+
+/// Parse one /proc/pressure file
+#[cfg(target_os = "linux")]
+fn parse_pressure(content: &str) -> Option<Pressure> {
+    // Format:
+    //   some avg10=0.00 avg60=0.00 avg300=0.00 total=0
+    //   full avg10=0.00 avg60=0.00 avg300=0.00 total=0
+
+    let mut some = None;
+    let mut full = None;
+
+    for line in content.lines() {
+        let mut parts = line.split_whitespace();
+        let kind = parts.next();
+
+        let mut avg = PressureAvg::default();
+        for part in parts {
+            let Some((key, value)) = part.split_once('=') else {
+                continue;
+            };
+            let Ok(value) = value.parse::<f64>() else {
+                continue;
+            };
+            match key {
+                "avg10" => avg.avg10 = value,
+                "avg60" => avg.avg60 = value,
+                "avg300" => avg.avg300 = value,
+                _ => {}
+            }
+        }
+
+        match kind {
+            Some("some") => some = Some(avg),
+            Some("full") => full = Some(avg),
+            _ => {}
+        }
+    }
+
+    Some(Pressure { some: some?, full })
+}
@@ -1,5 +1,12 @@
-use sysinfo::System;
-use std::collections::HashMap;
+use super::cgroup::cgroup_label;
+#[cfg(target_os = "linux")]
+use super::cgroup::process_cgroup;
+use super::rate::per_second;
+use super::ProcessNetData;
+use serde::{Deserialize, Serialize};
+use sysinfo::{System, ThreadKind};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum SortColumn {
@@ -7,9 +14,33 @@ pub enum SortColumn {
     Name,
     Cpu,
     Memory,
+    NetRx,
+    NetTx,
+    DiskRead,
+    DiskWrite,
 }
 
-#[derive(Clone)]
+/// How the process list is arranged
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProcessView {
+    #[default]
+    List,
+    Tree,     // Nested under parent processes
+    Cgroups,  // Grouped by cgroup (systemd unit, slice or container)
+}
+
+/// How the process list should be filtered, sorted and arranged
+pub struct ProcessOptions<'a> {
+    pub filter: &'a str,
+    pub sort_column: SortColumn,
+    pub sort_ascending: bool,
+    pub view: ProcessView,
+    pub io_only: bool,  // Only show processes currently reading or writing
+    pub expanded_cgroups: &'a HashSet<String>,  // Groups showing their member processes
+    pub collapsed_pids: &'a HashSet<u32>,  // Tree nodes hiding their descendants
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ProcessInfo {
     pub pid: u32,
     pub parent_pid: Option<u32>,
@@ -18,11 +49,69 @@ pub struct ProcessInfo {
     pub memory: u64,
     pub memory_percent: f64,
     pub status: String,
+    pub net_rx_rate: Option<u64>,  // bytes/s, None if unsupported
+    pub net_tx_rate: Option<u64>,
+    pub disk_read_rate: u64,   // bytes/s
+    pub disk_write_rate: u64,  // bytes/s
     pub depth: usize,  // For tree view indentation
+    // Missing from recordings made before cgroup grouping
+    #[serde(default)]
+    pub cgroup: Option<String>,
+    // A userland thread (Linux lists them as processes), whose usage its process already includes
+    #[serde(default)]
+    pub thread: bool,
+    // Set on the summary rows of the cgroup view, which aren't real processes
+    #[serde(skip)]
+    pub group: Option<ProcessGroup>,
+    // Set on rows of the tree view
+    #[serde(skip)]
+    pub tree: Option<TreeNode>,
+}
+
+/// Where a process sits in the tree view
+#[derive(Clone)]
+pub struct TreeNode {
+    pub guide: String,  // Box-drawing guides for every level above, e.g. "│ └─"
+    pub has_children: bool,
+    pub collapsed: Option<usize>,  // Processes folded into this row, whose usage it includes
+    pub context: bool,  // Doesn't match the filter, shown as an ancestor of a process that does
+}
+
+/// A cgroup's row in the cgroup view; CPU, memory and rates are summed over its members
+#[derive(Clone)]
+pub struct ProcessGroup {
+    pub count: usize,
+    pub expanded: bool,
+}
+
+impl ProcessInfo {
+    /// Key of the cgroup view group this process (or group row) belongs to
+    pub fn group_key(&self) -> &str {
+        self.cgroup.as_deref().unwrap_or("")
+    }
+
+    /// Add another process's usage to this row's totals
+    fn add_usage(&mut self, other: &ProcessInfo) {
+        if other.thread {
+            return;
+        }
+        self.cpu_usage += other.cpu_usage;
+        self.memory += other.memory;
+        self.memory_percent += other.memory_percent;
+        self.net_rx_rate = add_rate(self.net_rx_rate, other.net_rx_rate);
+        self.net_tx_rate = add_rate(self.net_tx_rate, other.net_tx_rate);
+        self.disk_read_rate += other.disk_read_rate;
+        self.disk_write_rate += other.disk_write_rate;
+    }
 }
 
 #[derive(Default)]
 pub struct ProcessData {
+    prev_disk_bytes: HashMap<u32, (u64, u64)>,  // pid -> (read, written)
+    last_update: Option<Instant>,
+    // Everything from the last update, filtered and arranged again when the view changes
+    collected: Vec<ProcessInfo>,
+    commands: HashMap<u32, String>,  // pid -> lowercased command line, for filtering
     pub processes: Vec<ProcessInfo>,
     pub total_processes: usize,
     pub running_processes: usize,
@@ -32,13 +121,21 @@ impl ProcessData {
     pub fn update(
         &mut self,
         system: &System,
-        filter: &str,
-        sort_column: SortColumn,
-        sort_ascending: bool,
-        tree_view: bool,
+        options: &ProcessOptions,
+        net_usage: &ProcessNetData,
     ) {
         let total_memory = system.total_memory();
 
+        // Disk rates use the measured time since the previous sample
+        let now = Instant::now();
+        let elapsed = self
+            .last_update
+            .map(|t| now.duration_since(t))
+            .unwrap_or(Duration::ZERO);
+        self.last_update = Some(now);
+        let mut disk_bytes: HashMap<u32, (u64, u64)> = HashMap::new();
+        self.commands.clear();
+
         self.processes.clear();
         self.total_processes = 0;
         self.running_processes = 0;
@@ -53,6 +150,18 @@ impl ProcessData {
                 self.running_processes += 1;
             }
 
+            let disk_usage = process.disk_usage();
+            let (read, written) = (disk_usage.total_read_bytes, disk_usage.total_written_bytes);
+            let (prev_read, prev_written) = self
+                .prev_disk_bytes
+                .get(&pid.as_u32())
+                .copied()
+                .unwrap_or((read, written));
+            disk_bytes.insert(pid.as_u32(), (read, written));
+
+            let disk_read_rate = per_second(prev_read, read, elapsed);
+            let disk_write_rate = per_second(prev_written, written, elapsed);
+
             let name = process.name().to_string_lossy().to_string();
             let cmd = process
                 .cmd()
@@ -60,17 +169,7 @@ impl ProcessData {
                 .map(|s| s.to_string_lossy().to_string())
                 .collect::<Vec<_>>()
                 .join(" ");
-
-            // Apply filter
-            if !filter.is_empty() {
-                let filter_lower = filter.to_lowercase();
-                let name_lower = name.to_lowercase();
-                let cmd_lower = cmd.to_lowercase();
-
-                if !name_lower.contains(&filter_lower) && !cmd_lower.contains(&filter_lower) {
-                    continue;
-                }
-            }
+            self.commands.insert(pid.as_u32(), cmd.to_lowercase());
 
             let memory = process.memory();
             let memory_percent = if total_memory > 0 {
@@ -80,6 +179,12 @@ impl ProcessData {
             };
 
             let parent_pid = process.parent().map(|p| p.as_u32());
+            let net = net_usage.get(pid.as_u32());
+
+            #[cfg(target_os = "linux")]
+            let cgroup = process_cgroup(pid.as_u32());
+            #[cfg(not(target_os = "linux"))]
+            let cgroup = None;
 
             all_processes.push(ProcessInfo {
                 pid: pid.as_u32(),
@@ -89,34 +194,70 @@ impl ProcessData {
                 memory,
                 memory_percent,
                 status,
+                net_rx_rate: net.map(|n| n.rx_rate),
+                net_tx_rate: net.map(|n| n.tx_rate),
+                disk_read_rate,
+                disk_write_rate,
                 depth: 0,
+                cgroup,
+                thread: matches!(process.thread_kind(), Some(ThreadKind::Userland)),
+                group: None,
+                tree: None,
             });
         }
 
-        if tree_view && filter.is_empty() {
-            // Build tree structure
-            self.processes = build_process_tree(all_processes);
+        self.prev_disk_bytes = disk_bytes;
+        self.collected = all_processes;
+        self.arrange(options);
+    }
+
+    /// Load a recorded process list, applying the current filter and sort
+    pub fn set_recorded(
+        &mut self,
+        processes: Vec<ProcessInfo>,
+        total_processes: usize,
+        running_processes: usize,
+        options: &ProcessOptions,
+    ) {
+        self.total_processes = total_processes;
+        self.running_processes = running_processes;
+
+        self.collected = processes;
+        self.commands.clear();
+        self.arrange(options);
+    }
+
+    /// Filter the collected processes and order them as a tree, cgroup groups or a sorted flat list
+    pub fn arrange(&mut self, options: &ProcessOptions) {
+        // Recordings don't keep command lines, so there the filter only matches names
+        let filter = options.filter.to_lowercase();
+        let matches = |p: &ProcessInfo| {
+            (filter.is_empty()
+                || p.name.to_lowercase().contains(&filter)
+                || self.commands.get(&p.pid).is_some_and(|cmd| cmd.contains(&filter)))
+                && (!options.io_only || p.disk_read_rate > 0 || p.disk_write_rate > 0)
+        };
+
+        if options.view == ProcessView::Tree {
+            self.processes = build_process_tree(&self.collected, &matches, options);
+            return;
+        }
+
+        let all_processes: Vec<ProcessInfo> = self.collected.iter().filter(|p| matches(p)).cloned().collect();
+        if options.view == ProcessView::Cgroups {
+            self.processes = build_cgroup_groups(all_processes, options);
         } else {
             // Flat list with sorting
             self.processes = all_processes;
-
-            self.processes.sort_by(|a, b| {
-                let cmp = match sort_column {
-                    SortColumn::Pid => a.pid.cmp(&b.pid),
-                    SortColumn::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
-                    SortColumn::Cpu => a.cpu_usage.partial_cmp(&b.cpu_usage).unwrap_or(std::cmp::Ordering::Equal),
-                    SortColumn::Memory => a.memory.cmp(&b.memory),
-                };
-
-                if sort_ascending {
-                    cmp
-                } else {
-                    cmp.reverse()
-                }
-            });
+            self.processes.sort_by(|a, b| compare(a, b, options));
         }
     }
 
+    /// Whether the last update saw this process, filtered out or not
+    pub fn has_process(&self, pid: u32) -> bool {
+        self.collected.iter().any(|p| p.pid == pid)
+    }
+
     pub fn format_memory(bytes: u64) -> String {
         const KB: u64 = 1024;
         const MB: u64 = KB * 1024;
@@ -134,13 +275,120 @@ impl ProcessData {
     }
 }
 
-fn build_process_tree(mut processes: Vec<ProcessInfo>) -> Vec<ProcessInfo> {
+/// Order two processes by the selected sort column and direction
+fn compare(a: &ProcessInfo, b: &ProcessInfo, options: &ProcessOptions) -> std::cmp::Ordering {
+    let cmp = match options.sort_column {
+        SortColumn::Pid => a.pid.cmp(&b.pid),
+        SortColumn::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+        SortColumn::Cpu => a.cpu_usage.partial_cmp(&b.cpu_usage).unwrap_or(std::cmp::Ordering::Equal),
+        SortColumn::Memory => a.memory.cmp(&b.memory),
+        SortColumn::NetRx => a.net_rx_rate.cmp(&b.net_rx_rate),
+        SortColumn::NetTx => a.net_tx_rate.cmp(&b.net_tx_rate),
+        SortColumn::DiskRead => a.disk_read_rate.cmp(&b.disk_read_rate),
+        SortColumn::DiskWrite => a.disk_write_rate.cmp(&b.disk_write_rate),
+    };
+
+    if options.sort_ascending {
+        cmp
+    } else {
+        cmp.reverse()
+    }
+}
+
+/// One summary row per cgroup, followed by its members when expanded
+fn build_cgroup_groups(processes: Vec<ProcessInfo>, options: &ProcessOptions) -> Vec<ProcessInfo> {
+    let mut members: HashMap<String, Vec<ProcessInfo>> = HashMap::new();
+    for proc in processes {
+        members.entry(proc.group_key().to_string()).or_default().push(proc);
+    }
+
+    let mut groups: Vec<(ProcessInfo, Vec<ProcessInfo>)> = members
+        .into_iter()
+        .map(|(key, mut procs)| {
+            procs.sort_by(|a, b| compare(a, b, options));
+
+            let mut summary = ProcessInfo {
+                // Lowest member PID, so sorting by PID keeps groups in a stable order
+                pid: procs.iter().map(|p| p.pid).min().unwrap_or(0),
+                parent_pid: None,
+                name: if key.is_empty() { "?".to_string() } else { cgroup_label(&key) },
+                cpu_usage: 0.0,
+                memory: 0,
+                memory_percent: 0.0,
+                status: String::new(),
+                net_rx_rate: None,
+                net_tx_rate: None,
+                disk_read_rate: 0,
+                disk_write_rate: 0,
+                depth: 0,
+                group: Some(ProcessGroup {
+                    count: procs.iter().filter(|p| !p.thread).count(),
+                    expanded: options.expanded_cgroups.contains(&key),
+                }),
+                cgroup: (!key.is_empty()).then_some(key),
+                thread: false,
+                tree: None,
+            };
+            for proc in &procs {
+                summary.add_usage(proc);
+            }
+            (summary, procs)
+        })
+        .collect();
+
+    groups.sort_by(|(a, _), (b, _)| compare(a, b, options));
+
+    let mut result = Vec::new();
+    for (summary, procs) in groups {
+        let expanded = summary.group.as_ref().is_some_and(|g| g.expanded);
+        result.push(summary);
+        if expanded {
+            result.extend(procs.into_iter().map(|proc| ProcessInfo { depth: 1, ..proc }));
+        }
+    }
+    result
+}
+
+/// Nest processes under their parents. Processes that don't match the filter are
+/// only kept as ancestors of ones that do.
+fn build_process_tree(
+    all_processes: &[ProcessInfo],
+    matches: &dyn Fn(&ProcessInfo) -> bool,
+    options: &ProcessOptions,
+) -> Vec<ProcessInfo> {
+    let index: HashMap<u32, usize> = all_processes.iter().enumerate().map(|(i, p)| (p.pid, i)).collect();
+    let mut keep: Vec<bool> = all_processes.iter().map(matches).collect();
+    let mut context: HashSet<u32> = HashSet::new();
+
+    for i in 0..all_processes.len() {
+        if !keep[i] || context.contains(&all_processes[i].pid) {
+            continue;
+        }
+        // Walk up until reaching a process that's already kept
+        let mut parent = all_processes[i].parent_pid;
+        while let Some(&j) = parent.and_then(|ppid| index.get(&ppid)) {
+            if keep[j] {
+                break;
+            }
+            keep[j] = true;
+            context.insert(all_processes[j].pid);
+            parent = all_processes[j].parent_pid;
+        }
+    }
+
+    let processes: Vec<ProcessInfo> = all_processes
+        .iter()
+        .zip(&keep)
+        .filter(|(_, &kept)| kept)
+        .map(|(proc, _)| proc.clone())
+        .collect();
+
     // Build a map of pid -> children
     let mut children_map: HashMap<u32, Vec<usize>> = HashMap::new();
     let mut root_indices: Vec<usize> = Vec::new();
 
     // Create a set of all PIDs for quick lookup
-    let all_pids: std::collections::HashSet<u32> = processes.iter().map(|p| p.pid).collect();
+    let all_pids: HashSet<u32> = processes.iter().map(|p| p.pid).collect();
 
     for (i, proc) in processes.iter().enumerate() {
         if let Some(ppid) = proc.parent_pid {
@@ -155,51 +403,105 @@ fn build_process_tree(mut processes: Vec<ProcessInfo>) -> Vec<ProcessInfo> {
         }
     }
 
-    // Sort roots by CPU usage (descending)
-    root_indices.sort_by(|&a, &b| {
-        processes[b].cpu_usage.partial_cmp(&processes[a].cpu_usage)
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
+    // Siblings follow the selected sort column
+    let by_column = |a: &usize, b: &usize| compare(&processes[*a], &processes[*b], options);
+    root_indices.sort_by(by_column);
+    for children in children_map.values_mut() {
+        children.sort_by(by_column);
+    }
+
+    let mut tree = TreeBuilder {
+        processes: &processes,
+        children_map: &children_map,
+        collapsed: options.collapsed_pids,
+        context: &context,
+        visited: HashSet::new(),
+        result: Vec::new(),
+    };
+    for &root_idx in &root_indices {
+        tree.add_with_children(root_idx, 0, "", true);
+    }
+
+    tree.result
+}
 
-    // Build flat tree list with depth
-    let mut result: Vec<ProcessInfo> = Vec::new();
+/// Flattens the process tree into rows, depth first
+struct TreeBuilder<'a> {
+    processes: &'a [ProcessInfo],
+    children_map: &'a HashMap<u32, Vec<usize>>,
+    collapsed: &'a HashSet<u32>,
+    context: &'a HashSet<u32>,
+    visited: HashSet<usize>,
+    result: Vec<ProcessInfo>,
+}
 
-    fn add_with_children(
-        idx: usize,
-        depth: usize,
-        processes: &mut Vec<ProcessInfo>,
-        children_map: &HashMap<u32, Vec<usize>>,
-        result: &mut Vec<ProcessInfo>,
-        visited: &mut std::collections::HashSet<usize>,
-    ) {
-        if visited.contains(&idx) {
+impl TreeBuilder<'_> {
+    /// Add a process and, unless it's collapsed, its descendants. `prefix` holds
+    /// the guides of the levels above, `last` whether it's its parent's last child.
+    fn add_with_children(&mut self, idx: usize, depth: usize, prefix: &str, last: bool) {
+        if !self.visited.insert(idx) {
             return;
         }
-        visited.insert(idx);
 
-        let mut proc = processes[idx].clone();
+        let mut proc = self.processes[idx].clone();
         proc.depth = depth;
-        let pid = proc.pid;
-        result.push(proc);
-
-        if let Some(children) = children_map.get(&pid) {
-            let mut sorted_children = children.clone();
-            // Sort children by CPU usage
-            sorted_children.sort_by(|&a, &b| {
-                processes[b].cpu_usage.partial_cmp(&processes[a].cpu_usage)
-                    .unwrap_or(std::cmp::Ordering::Equal)
-            });
+        let children_map = self.children_map;
+        let children: &[usize] = children_map.get(&proc.pid).map_or(&[], |c| c);
 
-            for &child_idx in &sorted_children {
-                add_with_children(child_idx, depth + 1, processes, children_map, result, visited);
+        let (guide, child_prefix) = match (depth, last) {
+            (0, _) => (String::new(), String::new()),
+            (_, true) => (format!("{}└─", prefix), format!("{}  ", prefix)),
+            (_, false) => (format!("{}├─", prefix), format!("{}│ ", prefix)),
+        };
+
+        if !children.is_empty() && self.collapsed.contains(&proc.pid) {
+            // Fold the whole subtree into this row
+            let mut hidden = 0;
+            for &child_idx in children {
+                self.fold(child_idx, &mut proc, &mut hidden);
             }
+            let context = self.context.contains(&proc.pid);
+            proc.tree = Some(TreeNode { guide, has_children: true, collapsed: Some(hidden), context });
+            self.result.push(proc);
+            return;
+        }
+
+        let context = self.context.contains(&proc.pid);
+        proc.tree = Some(TreeNode { guide, has_children: !children.is_empty(), collapsed: None, context });
+        self.result.push(proc);
+
+        for (i, &child_idx) in children.iter().enumerate() {
+            self.add_with_children(child_idx, depth + 1, &child_prefix, i + 1 == children.len());
         }
     }
 
-    let mut visited = std::collections::HashSet::new();
-    for &root_idx in &root_indices {
-        add_with_children(root_idx, 0, &mut processes, &children_map, &mut result, &mut visited);
+    /// Add a process and its descendants to a collapsed ancestor's totals
+    fn fold(&mut self, idx: usize, into: &mut ProcessInfo, hidden: &mut usize) {
+        if !self.visited.insert(idx) {
+            return;
+        }
+
+        let processes = self.processes;
+        let proc = &processes[idx];
+        // Threads are already in their process's usage, so they aren't counted either
+        if !proc.thread {
+            *hidden += 1;
+        }
+        into.add_usage(proc);
+
+        let children_map = self.children_map;
+        if let Some(children) = children_map.get(&proc.pid) {
+            for &child_idx in children {
+                self.fold(child_idx, into, hidden);
+            }
+        }
     }
+}
 
-    result
+/// Sum of two optional rates, None only if neither is known
+fn add_rate(a: Option<u64>, b: Option<u64>) -> Option<u64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a + b),
+        (a, b) => a.or(b),
+    }
 }
@@ -0,0 +1,256 @@
+use super::rate::per_second;
+use super::ConnectionData;
+use std::collections::HashMap;
+use std::time::Instant;
+
+#[derive(Clone, Copy, Default)]
+pub struct ProcessNetUsage {
+    pub rx_rate: u64, // bytes per second
+    pub tx_rate: u64, // bytes per second
+}
+
+/// Per-process network throughput, derived from per-socket byte counters
+#[derive(Default)]
+pub struct ProcessNetData {
+    prev_counters: HashMap<u64, (u64, u64)>, // socket inode -> (rx, tx)
+    last_update: Option<Instant>,
+    pub per_process: HashMap<u32, ProcessNetUsage>,
+    // Whether per-socket counters could be read on this system
+    pub available: bool,
+}
+
+impl ProcessNetData {
+    pub fn update(&mut self, connections: &ConnectionData) {
+        self.per_process.clear();
+
+        #[cfg(target_os = "linux")]
+        self.update_linux(connections);
+
+        #[cfg(not(target_os = "linux"))]
+        let _ = connections;
+    }
+
+    pub fn get(&self, pid: u32) -> Option<ProcessNetUsage> {
+        if self.available {
+            Some(self.per_process.get(&pid).copied().unwrap_or_default())
+        } else {
+            None
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn update_linux(&mut self, connections: &ConnectionData) {
+        let now = Instant::now();
+
+        let Some(counters) = tcp_socket_counters() else {
+            self.available = false;
+            return;
+        };
+        self.available = true;
+
+        let elapsed = self
+            .last_update
+            .map(|t| now.duration_since(t))
+            .unwrap_or(std::time::Duration::ZERO);
+
+        if !elapsed.is_zero() {
+            // Socket ownership was already resolved by the connection scan
+            let owners: HashMap<u64, u32> = connections
+                .connections
+                .iter()
+                .chain(connections.listening_ports.iter())
+                .filter(|c| c.inode != 0)
+                .filter_map(|c| c.pid.map(|pid| (c.inode, pid)))
+                .collect();
+
+            let mut deltas: HashMap<u32, (u64, u64)> = HashMap::new();
+            for (inode, &(rx, tx)) in &counters {
+                let (Some(&pid), Some(&(prev_rx, prev_tx))) =
+                    (owners.get(inode), self.prev_counters.get(inode))
+                else {
+                    continue;
+                };
+
+                let entry = deltas.entry(pid).or_default();
+                entry.0 += rx.saturating_sub(prev_rx);
+                entry.1 += tx.saturating_sub(prev_tx);
+            }
+
+            for (pid, (rx, tx)) in deltas {
+                self.per_process.insert(
+                    pid,
+                    ProcessNetUsage {
+                        rx_rate: per_second(0, rx, elapsed),
+                        tx_rate: per_second(0, tx, elapsed),
+                    },
+                );
+            }
+        }
+
+        self.prev_counters = counters;
+        self.last_update = Some(now);
+    }
+}
+
+#[cfg(target_os = "linux")]
+const SOCK_DIAG_BY_FAMILY: u16 = 20;
+#[cfg(target_os = "linux")]
+const INET_DIAG_INFO: u16 = 2;
+#[cfg(target_os = "linux")]
+const NLMSG_HDR_LEN: usize = 16;
+#[cfg(target_os = "linux")]
+const INET_DIAG_REQ_LEN: usize = 56;
+#[cfg(target_os = "linux")]
+const INET_DIAG_MSG_LEN: usize = 72;
+// Offsets of tcpi_bytes_acked / tcpi_bytes_received in struct tcp_info
+#[cfg(target_os = "linux")]
+const TCPI_BYTES_ACKED: usize = 120;
+#[cfg(target_os = "linux")]
+const TCPI_BYTES_RECEIVED: usize = 128;
+
+/// Dump (rx, tx) byte counters of every TCP socket, keyed by inode,
+/// using a NETLINK_SOCK_DIAG request with the INET_DIAG_INFO extension
+#[cfg(target_os = "linux")]
+fn tcp_socket_counters() -> Option<HashMap<u64, (u64, u64)>> {
+    let fd = unsafe {
+        libc::socket(
+            libc::AF_NETLINK,
+            libc::SOCK_DGRAM | libc::SOCK_CLOEXEC,
+            libc::NETLINK_SOCK_DIAG,
+        )
+    };
+    if fd < 0 {
+        return None;
+    }
+
+    let mut counters = HashMap::new();
+    let ok = [libc::AF_INET, libc::AF_INET6]
+        .iter()
+        .all(|&family| dump_tcp_family(fd, family as u8, &mut counters));
+
+    unsafe {
+        libc::close(fd);
+    }
+
+    ok.then_some(counters)
+}
+
+#[cfg(target_os = "linux")]
+fn dump_tcp_family(fd: i32, family: u8, counters: &mut HashMap<u64, (u64, u64)>) -> bool {
+    let total_len = NLMSG_HDR_LEN + INET_DIAG_REQ_LEN;
+
+    // struct nlmsghdr
+    let mut req: Vec<u8> = Vec::with_capacity(total_len);
+    req.extend_from_slice(&(total_len as u32).to_ne_bytes());
+    req.extend_from_slice(&SOCK_DIAG_BY_FAMILY.to_ne_bytes());
+    req.extend_from_slice(&((libc::NLM_F_REQUEST | libc::NLM_F_DUMP) as u16).to_ne_bytes());
+    req.extend_from_slice(&0u32.to_ne_bytes()); // seq
+    req.extend_from_slice(&0u32.to_ne_bytes()); // pid
+
+    // struct inet_diag_req_v2, with an all-zero inet_diag_sockid
+    req.push(family);
+    req.push(libc::IPPROTO_TCP as u8);
+    req.push(1 << (INET_DIAG_INFO - 1));
+    req.push(0);
+    req.extend_from_slice(&u32::MAX.to_ne_bytes()); // all states
+    req.resize(total_len, 0);
+
+    let mut kernel: libc::sockaddr_nl = unsafe { std::mem::zeroed() };
+    kernel.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+
+    let sent = unsafe {
+        libc::sendto(
+            fd,
+            req.as_ptr() as *const libc::c_void,
+            req.len(),
+            0,
+            &kernel as *const libc::sockaddr_nl as *const libc::sockaddr,
+            std::mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+        )
+    };
+    if sent < 0 {
+        return false;
+    }
+
+    let mut buf = vec![0u8; 32 * 1024];
+    loop {
+        let received = unsafe { libc::recv(fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len(), 0) };
+        if received <= 0 {
+            return false;
+        }
+
+        let received = received as usize;
+        let mut offset = 0;
+        while offset + NLMSG_HDR_LEN <= received {
+            let len = read_u32(&buf, offset) as usize;
+            let msg_type = read_u16(&buf, offset + 4);
+            if len < NLMSG_HDR_LEN || offset + len > received {
+                break;
+            }
+
+            match msg_type as i32 {
+                libc::NLMSG_DONE => return true,
+                libc::NLMSG_ERROR => return false,
+                _ => parse_diag_msg(&buf[offset + NLMSG_HDR_LEN..offset + len], counters),
+            }
+
+            offset += align4(len);
+        }
+    }
+}
+
+/// Parse one struct inet_diag_msg followed by its attributes
+#[cfg(target_os = "linux")]
+fn parse_diag_msg(payload: &[u8], counters: &mut HashMap<u64, (u64, u64)>) {
+    if payload.len() < INET_DIAG_MSG_LEN {
+        return;
+    }
+
+    // idiag_inode is the last field of struct inet_diag_msg
+    let inode = read_u32(payload, INET_DIAG_MSG_LEN - 4) as u64;
+    if inode == 0 {
+        return;
+    }
+
+    let mut offset = INET_DIAG_MSG_LEN;
+    while offset + 4 <= payload.len() {
+        let attr_len = read_u16(payload, offset) as usize;
+        let attr_type = read_u16(payload, offset + 2);
+        if attr_len < 4 || offset + attr_len > payload.len() {
+            break;
+        }
+
+        let info = &payload[offset + 4..offset + attr_len];
+        if attr_type == INET_DIAG_INFO && info.len() >= TCPI_BYTES_RECEIVED + 8 {
+            let tx = read_u64(info, TCPI_BYTES_ACKED);
+            let rx = read_u64(info, TCPI_BYTES_RECEIVED);
+            counters.insert(inode, (rx, tx));
+        }
+
+        offset += align4(attr_len);
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn align4(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+#[cfg(target_os = "linux")]
+fn read_u16(buf: &[u8], offset: usize) -> u16 {
+    u16::from_ne_bytes([buf[offset], buf[offset + 1]])
+}
+
+#[cfg(target_os = "linux")]
+fn read_u32(buf: &[u8], offset: usize) -> u32 {
+    let mut bytes = [0u8; 4];
+    bytes.copy_from_slice(&buf[offset..offset + 4]);
+    u32::from_ne_bytes(bytes)
+}
+
+#[cfg(target_os = "linux")]
+fn read_u64(buf: &[u8], offset: usize) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&buf[offset..offset + 8]);
+    u64::from_ne_bytes(bytes)
+}
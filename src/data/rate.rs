@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+/// Convert the change of a monotonically increasing counter into a per-second rate.
+///
+/// Counter resets (current < previous) and zero-length intervals yield 0.
+pub fn per_second(previous: u64, current: u64, elapsed: Duration) -> u64 {
+    let secs = elapsed.as_secs_f64();
+    if secs <= 0.0 {
+        return 0;
+    }
+
+    (current.saturating_sub(previous) as f64 / secs).round() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feed counter samples taken `interval` apart the way the collectors do,
+    /// with the first sample as its own baseline
+    fn rates(counters: &[u64], interval: Duration) -> Vec<u64> {
+        let mut previous = None;
+        counters
+            .iter()
+            .map(|&current| {
+                let (prev, elapsed) = previous.map_or((current, Duration::ZERO), |prev| (prev, interval));
+                previous = Some(current);
+                per_second(prev, current, elapsed)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rate_at_quarter_second_interval() {
+        // 250 bytes every 250ms is 1000 bytes/s
+        let counters = [10_000, 10_250, 10_500, 10_750];
+        assert_eq!(rates(&counters, Duration::from_millis(250)), [0, 1000, 1000, 1000]);
+    }
+
+    #[test]
+    fn rate_at_one_second_interval() {
+        let counters = [0, 1_500, 1_500, 4_000];
+        assert_eq!(rates(&counters, Duration::from_secs(1)), [0, 1500, 0, 2500]);
+    }
+
+    #[test]
+    fn rate_at_two_second_interval() {
+        // The same traffic spread over twice the time is half the rate
+        let counters = [0, 1_500, 1_500, 4_000];
+        assert_eq!(rates(&counters, Duration::from_secs(2)), [0, 750, 0, 1250]);
+    }
+
+    #[test]
+    fn same_traffic_gives_same_rate_at_any_interval() {
+        // 8000 bytes/s sampled at different intervals
+        for millis in [250, 1000, 2000] {
+            let step = 8 * millis;
+            let counters: Vec<u64> = (0..5).map(|i| 1_000_000 + i * step).collect();
+            assert_eq!(rates(&counters, Duration::from_millis(millis))[1..], [8000; 4]);
+        }
+    }
+
+    #[test]
+    fn counter_reset_gives_zero() {
+        // An interface going down and up again starts counting from 0
+        assert_eq!(per_second(5_000_000, 1_000, Duration::from_secs(1)), 0);
+
+        // A 32-bit counter wrapping around
+        assert_eq!(per_second(u32::MAX as u64 - 10, 20, Duration::from_secs(1)), 0);
+
+        let counters = [1_000, 2_000, 500, 1_500];
+        assert_eq!(rates(&counters, Duration::from_secs(1)), [0, 1000, 0, 1000]);
+    }
+
+    #[test]
+    fn zero_interval_gives_zero() {
+        assert_eq!(per_second(0, 1_000_000, Duration::ZERO), 0);
+        assert_eq!(per_second(1_000, 1_000, Duration::ZERO), 0);
+    }
+}
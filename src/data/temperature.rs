@@ -1,11 +1,15 @@
+use serde::{Deserialize, Serialize};
 use sysinfo::Components;
 
+#[derive(Serialize, Deserialize)]
 pub struct TemperatureData {
+    #[serde(skip)]
     components: Components,
     pub sensors: Vec<SensorInfo>,
 }
 
 #[allow(dead_code)]
+#[derive(Serialize, Deserialize)]
 pub struct SensorInfo {
     pub label: String,
     pub temperature: f32,
@@ -0,0 +1,317 @@
+use crate::app::App;
+use crate::data::BatteryState;
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// How long a client gets to send its request line before the connection is dropped
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Run the collectors on a background task and serve their latest values
+/// in Prometheus text exposition format at /metrics
+pub async fn serve(app: App, listen: SocketAddr, interval: Duration) -> std::io::Result<()> {
+    let metrics = Arc::new(RwLock::new(render(&app)));
+
+    tokio::spawn(collect(app, interval, Arc::clone(&metrics)));
+
+    let listener = TcpListener::bind(listen).await?;
+    eprintln!("Serving metrics on http://{}/metrics", listener.local_addr()?);
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let metrics = Arc::clone(&metrics);
+        tokio::spawn(async move {
+            // A client hanging up mid-request is not worth reporting
+            let _ = handle_connection(stream, &metrics).await;
+        });
+    }
+}
+
+async fn collect(app: App, interval: Duration, metrics: Arc<RwLock<String>>) {
+    let app = Arc::new(Mutex::new(app));
+    let mut ticker = tokio::time::interval(interval);
+    ticker.tick().await;
+
+    loop {
+        ticker.tick().await;
+
+        // Collectors read /proc and friends synchronously, so keep them off the async workers
+        let app = Arc::clone(&app);
+        let result = tokio::task::spawn_blocking(move || {
+            // A panic mid-update poisons the lock, but the next update overwrites the data anyway
+            let mut app = app.lock().unwrap_or_else(PoisonError::into_inner);
+            app.update();
+            render(&app)
+        })
+        .await;
+
+        match result {
+            Ok(text) => {
+                if let Ok(mut metrics) = metrics.write() {
+                    *metrics = text;
+                }
+            }
+            Err(e) => eprintln!("Error: collecting metrics failed: {}", e),
+        }
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream, metrics: &RwLock<String>) -> std::io::Result<()> {
+    let mut buf = [0u8; 1024];
+    let read = tokio::time::timeout(READ_TIMEOUT, stream.read(&mut buf))
+        .await
+        .map_err(|_| std::io::Error::from(std::io::ErrorKind::TimedOut))??;
+    let request = String::from_utf8_lossy(&buf[..read]);
+    let mut parts = request.lines().next().unwrap_or("").split_whitespace();
+
+    let response = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => {
+            let body = metrics.read().map(|m| m.clone()).unwrap_or_default();
+            http_response("200 OK", "text/plain; version=0.0.4; charset=utf-8", &body)
+        }
+        (Some("GET"), Some("/")) => http_response(
+            "200 OK",
+            "text/html; charset=utf-8",
+            "<html><body><a href=\"/metrics\">Metrics</a></body></html>\n",
+        ),
+        (Some("GET"), _) => http_response("404 Not Found", "text/plain", "Not Found\n"),
+        _ => http_response("405 Method Not Allowed", "text/plain", "Method Not Allowed\n"),
+    };
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+fn http_response(status: &str, content_type: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )
+}
+
+/// Render the app's current data in Prometheus text exposition format
+pub fn render(app: &App) -> String {
+    let mut out = String::new();
+
+    // CPU
+    metric(&mut out, "mprobe_cpu_usage_percent", "gauge", "Total CPU usage across all cores");
+    sample(&mut out, "mprobe_cpu_usage_percent", &[], app.cpu_data.total_usage);
+
+    if let Some(times) = &app.cpu_data.times {
+        metric(&mut out, "mprobe_cpu_time_percent", "gauge", "Share of CPU time spent in each state");
+        for (mode, value) in times.parts() {
+            sample(&mut out, "mprobe_cpu_time_percent", &[("mode", mode)], value);
+        }
+    }
+
+    metric(&mut out, "mprobe_cpu_core_usage_percent", "gauge", "CPU usage per core");
+    for (i, usage) in app.cpu_data.per_core_usage.iter().enumerate() {
+        sample(&mut out, "mprobe_cpu_core_usage_percent", &[("core", &i.to_string())], *usage);
+    }
+
+    metric(&mut out, "mprobe_cpu_core_frequency_mhz", "gauge", "CPU frequency per core");
+    for (i, freq) in app.cpu_data.per_core_freq.iter().enumerate() {
+        sample(&mut out, "mprobe_cpu_core_frequency_mhz", &[("core", &i.to_string())], *freq as f64);
+    }
+
+    let (l1, l5, l15) = app.load_avg;
+    metric(&mut out, "mprobe_load_average", "gauge", "System load average");
+    sample(&mut out, "mprobe_load_average", &[("period", "1m")], l1);
+    sample(&mut out, "mprobe_load_average", &[("period", "5m")], l5);
+    sample(&mut out, "mprobe_load_average", &[("period", "15m")], l15);
+
+    metric(&mut out, "mprobe_uptime_seconds", "gauge", "System uptime");
+    sample(&mut out, "mprobe_uptime_seconds", &[], app.uptime as f64);
+
+    // Pressure stall information
+    if app.pressure_data.is_available() {
+        metric(&mut out, "mprobe_pressure_percent", "gauge", "Share of time tasks stalled waiting on a resource");
+        for (resource, pressure) in app.pressure_data.resources() {
+            let Some(pressure) = pressure else {
+                continue;
+            };
+            let averages = std::iter::once(("some", pressure.some)).chain(pressure.full.map(|full| ("full", full)));
+            for (kind, avg) in averages {
+                for (window, value) in [("10s", avg.avg10), ("60s", avg.avg60), ("300s", avg.avg300)] {
+                    let labels = [("resource", resource), ("kind", kind), ("window", window)];
+                    sample(&mut out, "mprobe_pressure_percent", &labels, value);
+                }
+            }
+        }
+    }
+
+    // Memory and swap
+    let mem = &app.memory_data;
+    for (name, help, value) in [
+        ("mprobe_memory_total_bytes", "Total physical memory", mem.total),
+        ("mprobe_memory_used_bytes", "Used physical memory", mem.used),
+        ("mprobe_memory_available_bytes", "Memory available for new allocations", mem.available),
+        ("mprobe_memory_cached_bytes", "Page cache", mem.cached),
+        ("mprobe_swap_total_bytes", "Total swap space", mem.swap_total),
+        ("mprobe_swap_used_bytes", "Used swap space", mem.swap_used),
+    ] {
+        metric(&mut out, name, "gauge", help);
+        sample(&mut out, name, &[], value as f64);
+    }
+
+    if let Some(details) = &mem.details {
+        metric(&mut out, "mprobe_memory_detail_bytes", "gauge", "Memory breakdown from /proc/meminfo");
+        for (kind, value) in [
+            ("anon", details.anon),
+            ("buffers", details.buffers),
+            ("shmem", details.shmem),
+            ("slab_reclaimable", details.slab_reclaimable),
+            ("slab_unreclaimable", details.slab_unreclaimable),
+            ("kernel_stack", details.kernel_stack),
+            ("page_tables", details.page_tables),
+            ("dirty", details.dirty),
+            ("writeback", details.writeback),
+            ("hugepages_total", details.hugepages_total),
+            ("hugepages_free", details.hugepages_free),
+        ] {
+            sample(&mut out, "mprobe_memory_detail_bytes", &[("kind", kind)], value as f64);
+        }
+    }
+
+    // Disk capacity and I/O
+    metric(&mut out, "mprobe_disk_total_bytes", "gauge", "Filesystem size");
+    for disk in &app.disk_data.disk_info {
+        sample(&mut out, "mprobe_disk_total_bytes", &[("mountpoint", &disk.mount_point)], disk.total as f64);
+    }
+    metric(&mut out, "mprobe_disk_used_bytes", "gauge", "Filesystem space in use");
+    for disk in &app.disk_data.disk_info {
+        sample(&mut out, "mprobe_disk_used_bytes", &[("mountpoint", &disk.mount_point)], disk.used as f64);
+    }
+
+    metric(&mut out, "mprobe_disk_read_bytes_per_second", "gauge", "Block device read throughput");
+    for dev in &app.disk_data.io_devices {
+        sample(&mut out, "mprobe_disk_read_bytes_per_second", &[("device", &dev.name)], dev.read_rate as f64);
+    }
+    metric(&mut out, "mprobe_disk_write_bytes_per_second", "gauge", "Block device write throughput");
+    for dev in &app.disk_data.io_devices {
+        sample(&mut out, "mprobe_disk_write_bytes_per_second", &[("device", &dev.name)], dev.write_rate as f64);
+    }
+    metric(&mut out, "mprobe_disk_busy_percent", "gauge", "Share of time a block device had I/O in flight");
+    for dev in &app.disk_data.io_devices {
+        sample(&mut out, "mprobe_disk_busy_percent", &[("device", &dev.name)], dev.busy_percent);
+    }
+
+    // Network
+    let interfaces = &app.network_data.interfaces;
+    metric(&mut out, "mprobe_network_receive_bytes_total", "counter", "Bytes received per interface");
+    for iface in interfaces {
+        sample(&mut out, "mprobe_network_receive_bytes_total", &[("interface", &iface.name)], iface.total_received as f64);
+    }
+    metric(&mut out, "mprobe_network_transmit_bytes_total", "counter", "Bytes transmitted per interface");
+    for iface in interfaces {
+        sample(&mut out, "mprobe_network_transmit_bytes_total", &[("interface", &iface.name)], iface.total_transmitted as f64);
+    }
+    metric(&mut out, "mprobe_network_receive_errors_total", "counter", "Receive errors per interface");
+    for iface in interfaces {
+        sample(&mut out, "mprobe_network_receive_errors_total", &[("interface", &iface.name)], iface.errors_rx as f64);
+    }
+    metric(&mut out, "mprobe_network_transmit_errors_total", "counter", "Transmit errors per interface");
+    for iface in interfaces {
+        sample(&mut out, "mprobe_network_transmit_errors_total", &[("interface", &iface.name)], iface.errors_tx as f64);
+    }
+
+    // Sensors
+    // Chips often repeat labels ("temp1", "Composite"), so the index keeps series unique
+    metric(&mut out, "mprobe_temperature_celsius", "gauge", "Temperature sensor readings");
+    for (i, sensor) in app.temperature_data.sensors.iter().enumerate() {
+        sample(
+            &mut out,
+            "mprobe_temperature_celsius",
+            &[("index", &i.to_string()), ("sensor", &sensor.label)],
+            sensor.temperature as f64,
+        );
+    }
+
+    metric(&mut out, "mprobe_battery_percent", "gauge", "Battery charge level");
+    for (i, battery) in app.battery_data.batteries.iter().enumerate() {
+        sample(&mut out, "mprobe_battery_percent", &[("battery", &i.to_string())], battery.percentage as f64);
+    }
+
+    // One series per state, 1 for the current one, so state changes don't start new series
+    metric(&mut out, "mprobe_battery_state", "gauge", "Battery charging state (1 for the current state)");
+    for (i, battery) in app.battery_data.batteries.iter().enumerate() {
+        for (name, state) in [
+            ("charging", BatteryState::Charging),
+            ("discharging", BatteryState::Discharging),
+            ("full", BatteryState::Full),
+            ("not_charging", BatteryState::NotCharging),
+            ("unknown", BatteryState::Unknown),
+        ] {
+            let value = if battery.state == state { 1.0 } else { 0.0 };
+            sample(&mut out, "mprobe_battery_state", &[("battery", &i.to_string()), ("state", name)], value);
+        }
+    }
+
+    // Processes
+    metric(&mut out, "mprobe_processes", "gauge", "Number of processes");
+    sample(&mut out, "mprobe_processes", &[], app.process_data.total_processes as f64);
+    metric(&mut out, "mprobe_processes_running", "gauge", "Number of running processes");
+    sample(&mut out, "mprobe_processes_running", &[], app.process_data.running_processes as f64);
+
+    // Cgroup limits; memory and CPU usage above are already relative to them
+    let cgroup = &app.cgroup_data;
+    for (name, help, value) in [
+        ("mprobe_cgroup_memory_limit_bytes", "Memory limit of mprobe's cgroup", cgroup.memory_max.map(|v| v as f64)),
+        ("mprobe_cgroup_cpu_limit_cores", "CPU quota of mprobe's cgroup", cgroup.cpu_limit),
+        ("mprobe_cgroup_pids_limit", "Task limit of mprobe's cgroup", cgroup.pids_max.map(|v| v as f64)),
+        ("mprobe_cgroup_pids", "Tasks in mprobe's cgroup", cgroup.pids_current.map(|v| v as f64)),
+    ] {
+        if let Some(value) = value {
+            metric(&mut out, name, "gauge", help);
+            sample(&mut out, name, &[], value);
+        }
+    }
+
+    out
+}
+
+fn metric(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+fn sample(out: &mut String, name: &str, labels: &[(&str, &str)], value: f64) {
+    if labels.is_empty() {
+        let _ = writeln!(out, "{} {}", name, format_value(value));
+        return;
+    }
+
+    let labels: Vec<String> = labels
+        .iter()
+        .map(|(key, value)| format!("{}=\"{}\"", key, escape_label(value)))
+        .collect();
+    let _ = writeln!(out, "{}{{{}}} {}", name, labels.join(","), format_value(value));
+}
+
+/// Format a sample value; the exposition format spells non-finite values
+/// `+Inf`, `-Inf` and `NaN` where Rust prints `inf` and `NaN`
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Escape a label value as required by the exposition format
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -0,0 +1,219 @@
+use crate::config::{LayoutConfig, PanelConfig, RowConfig};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+
+/// Narrowest a panel can be drawn before its contents stop making sense
+const MIN_PANEL_WIDTH: u16 = 24;
+
+/// A panel that can be placed in the overview
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PanelKind {
+    Cpu,
+    Memory,
+    Network,
+    Disk,
+    System,
+    Sensors,
+    Pressure,
+    Processes,  // Swaps for connections or alerts when those are toggled
+    Connections,
+    Alerts,
+}
+
+impl PanelKind {
+    const NAMES: &'static str = "cpu, memory, network, disk, system, sensors, pressure, processes, connections, alerts";
+
+    fn parse(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "cpu" => Ok(PanelKind::Cpu),
+            "memory" | "mem" => Ok(PanelKind::Memory),
+            "network" | "net" => Ok(PanelKind::Network),
+            "disk" | "disks" => Ok(PanelKind::Disk),
+            "system" => Ok(PanelKind::System),
+            "sensors" => Ok(PanelKind::Sensors),
+            "pressure" | "psi" => Ok(PanelKind::Pressure),
+            "processes" | "process" => Ok(PanelKind::Processes),
+            "connections" => Ok(PanelKind::Connections),
+            "alerts" => Ok(PanelKind::Alerts),
+            _ => Err(format!("unknown panel kind '{}' (expected one of {})", name, Self::NAMES)),
+        }
+    }
+
+    /// Scrollable tables, which take the leftover space in compact layouts
+    pub fn is_list(self) -> bool {
+        matches!(self, PanelKind::Processes | PanelKind::Connections | PanelKind::Alerts)
+    }
+}
+
+/// The validated overview layout: rows top to bottom, panels left to right
+#[derive(Debug, Clone)]
+pub struct PanelLayout {
+    rows: Vec<(Constraint, Vec<(PanelKind, Constraint)>)>,
+}
+
+impl Default for PanelLayout {
+    fn default() -> Self {
+        Self::from_config(&LayoutConfig::default()).expect("default layout is valid")
+    }
+}
+
+impl PanelLayout {
+    /// Validate a layout from the config file, naming the offending entry on error
+    pub fn from_config(config: &LayoutConfig) -> Result<Self, String> {
+        if config.rows.is_empty() {
+            return Err("layout needs at least one row".to_string());
+        }
+
+        let rows = config
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| parse_row(row).map_err(|e| format!("layout.rows[{}]: {}", i, e)))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { rows })
+    }
+
+    /// Every panel kind in the layout, in reading order, without repeats
+    pub fn kinds(&self) -> Vec<PanelKind> {
+        let mut kinds = Vec::new();
+        for (kind, _) in self.rows.iter().flat_map(|(_, row)| row) {
+            if !kinds.contains(kind) {
+                kinds.push(*kind);
+            }
+        }
+        kinds
+    }
+
+    /// Whether every row gets its fixed or minimum height and every panel a usable width
+    pub fn fits(&self, area: Rect) -> bool {
+        // Summed as u32 so sizes past u16::MAX don't overflow
+        let min_size = |constraint: &Constraint| match constraint {
+            Constraint::Length(n) | Constraint::Min(n) => u32::from(*n),
+            _ => 0,
+        };
+
+        let height: u32 = self.rows.iter().map(|(height, _)| min_size(height)).sum();
+        height <= u32::from(area.height)
+            && self.rows.iter().all(|(_, row)| {
+                row.iter()
+                    .map(|(_, width)| min_size(width).max(u32::from(MIN_PANEL_WIDTH)))
+                    .sum::<u32>()
+                    <= u32::from(area.width)
+            })
+    }
+
+    /// Place every panel inside `area`
+    pub fn split(&self, area: Rect) -> Vec<(PanelKind, Rect)> {
+        let row_areas = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(self.rows.iter().map(|(height, _)| *height))
+            .split(area);
+
+        let mut panels = Vec::new();
+        for ((_, row), row_area) in self.rows.iter().zip(row_areas.iter()) {
+            let panel_areas = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(row.iter().map(|(_, width)| *width))
+                .split(*row_area);
+
+            for ((kind, _), panel_area) in row.iter().zip(panel_areas.iter()) {
+                panels.push((*kind, *panel_area));
+            }
+        }
+
+        panels
+    }
+}
+
+fn parse_row(row: &RowConfig) -> Result<(Constraint, Vec<(PanelKind, Constraint)>), String> {
+    let height = parse_size(&row.height).map_err(|e| format!("height: {}", e))?;
+
+    if row.panels.is_empty() {
+        return Err("row has no panels".to_string());
+    }
+
+    let panels = row
+        .panels
+        .iter()
+        .enumerate()
+        .map(|(i, panel)| parse_panel(panel).map_err(|e| format!("panels[{}]: {}", i, e)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let percent: u16 = panels
+        .iter()
+        .map(|(_, width)| match width {
+            Constraint::Percentage(p) => *p,
+            _ => 0,
+        })
+        .sum();
+    if percent > 100 {
+        return Err(format!("panel widths add up to {}%", percent));
+    }
+
+    Ok((height, panels))
+}
+
+fn parse_panel(panel: &PanelConfig) -> Result<(PanelKind, Constraint), String> {
+    let kind = PanelKind::parse(&panel.kind)?;
+    let width = parse_size(&panel.width).map_err(|e| format!("width: {}", e))?;
+    Ok((kind, width))
+}
+
+/// Parse a size: "11" (cells), "50%", "8+" (at least 8 cells) or "*" (share the rest)
+fn parse_size(s: &str) -> Result<Constraint, String> {
+    let s = s.trim();
+    let invalid = || format!("invalid size '{}' (expected N, N%, N+ or *)", s);
+
+    if s == "*" {
+        Ok(Constraint::Fill(1))
+    } else if let Some(percent) = s.strip_suffix('%') {
+        let percent: u16 = percent.trim().parse().map_err(|_| invalid())?;
+        if percent > 100 {
+            return Err(format!("{}% is more than 100%", percent));
+        }
+        Ok(Constraint::Percentage(percent))
+    } else if let Some(min) = s.strip_suffix('+') {
+        Ok(Constraint::Min(min.trim().parse().map_err(|_| invalid())?))
+    } else {
+        Ok(Constraint::Length(s.parse().map_err(|_| invalid())?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(rows: &[(&str, &[&str])]) -> PanelLayout {
+        let config = LayoutConfig {
+            rows: rows
+                .iter()
+                .map(|(height, widths)| RowConfig {
+                    height: height.to_string(),
+                    panels: widths
+                        .iter()
+                        .map(|width| PanelConfig { kind: "cpu".to_string(), width: width.to_string() })
+                        .collect(),
+                })
+                .collect(),
+        };
+        PanelLayout::from_config(&config).expect("valid layout")
+    }
+
+    #[test]
+    fn fits_when_sizes_add_up() {
+        let layout = parse(&[("10", &["30", "*"]), ("5+", &["50%", "50%"])]);
+        assert!(layout.fits(Rect::new(0, 0, 80, 15)));
+        assert!(!layout.fits(Rect::new(0, 0, 80, 14)));
+        assert!(!layout.fits(Rect::new(0, 0, 53, 15)));
+    }
+
+    #[test]
+    fn huge_sizes_do_not_overflow() {
+        // 2 × 40000 wraps around in u16 and would look like it fits
+        let layout = parse(&[("40000", &["*"]), ("40000", &["*"])]);
+        assert!(!layout.fits(Rect::new(0, 0, u16::MAX, u16::MAX)));
+
+        let layout = parse(&[("1", &["40000", "40000"])]);
+        assert!(!layout.fits(Rect::new(0, 0, u16::MAX, u16::MAX)));
+    }
+}
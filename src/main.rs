@@ -1,34 +1,53 @@
+mod alert;
 mod app;
 mod config;
 mod data;
+mod exporter;
+mod layout;
+mod record;
+mod snapshot;
+mod source;
 mod ui;
 
-use std::io;
+use std::io::{self, Write};
+use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use app::{App, KillSignal};
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use config::Config;
+use ui::{ColorDepth, ThemeSet};
+use record::{RecordFormat, Recorder};
+use snapshot::Snapshot;
+use source::ReplaySource;
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers, MouseEventKind},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use ratatui::{backend::CrosstermBackend, Terminal};
+use ratatui::{backend::CrosstermBackend, layout::{Position, Rect}, Terminal};
 
 /// A beautiful terminal-based system monitor
 #[derive(Parser, Debug)]
 #[command(name = "mprobe")]
 #[command(author, version, about, long_about = None)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Update interval in milliseconds
     #[arg(short = 'i', long, value_name = "MS")]
     update_interval: Option<u64>,
 
-    /// Disable colors (use default terminal colors)
+    /// Disable colors (use default terminal colors); same as --color=none
     #[arg(long)]
     no_color: bool,
 
+    /// Color support, detected from the terminal when not given
+    #[arg(long, value_enum, value_name = "DEPTH")]
+    color: Option<ColorDepth>,
+
     /// Generate default config file at ~/.config/mprobe/config.toml
     #[arg(long)]
     generate_config: bool,
@@ -36,6 +55,73 @@ struct Args {
     /// Show config file path
     #[arg(long)]
     config_path: bool,
+
+    /// Print a single snapshot as text and exit, without the TUI
+    #[arg(long)]
+    once: bool,
+
+    /// Print a single snapshot as JSON and exit, without the TUI
+    #[arg(long)]
+    json: bool,
+
+    /// Number of top processes to include in a snapshot
+    #[arg(long, value_name = "N", default_value_t = 10)]
+    top: usize,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Record samples to a file without a terminal UI
+    Record {
+        /// File to append samples to
+        #[arg(short, long, value_name = "FILE")]
+        output: PathBuf,
+
+        /// Sampling interval in milliseconds (defaults to the configured update interval)
+        #[arg(short, long, value_name = "MS")]
+        interval: Option<u64>,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = RecordFormat::Ndjson)]
+        format: RecordFormat,
+
+        /// Rotate the file once it exceeds this size (e.g. 500K, 10M, 1G)
+        #[arg(long, value_name = "SIZE", value_parser = record::parse_size)]
+        max_size: Option<u64>,
+
+        /// Number of rotated files to keep
+        #[arg(long, value_name = "N", default_value_t = 5)]
+        keep: usize,
+
+        /// Stop after this many samples (runs until interrupted otherwise)
+        #[arg(short = 'n', long, value_name = "N")]
+        samples: Option<u64>,
+
+        /// Number of top processes to include in each NDJSON sample
+        #[arg(long, value_name = "N", default_value_t = 10)]
+        top: usize,
+    },
+
+    /// Serve metrics in Prometheus text format at /metrics
+    Serve {
+        /// Address to listen on
+        #[arg(short, long, value_name = "ADDR", default_value = "127.0.0.1:9101")]
+        listen: SocketAddr,
+
+        /// Collection interval in milliseconds (defaults to the configured update interval)
+        #[arg(short, long, value_name = "MS")]
+        interval: Option<u64>,
+    },
+
+    /// Replay an NDJSON recording in the terminal UI
+    Replay {
+        /// Recording made with `mprobe record`
+        file: PathBuf,
+
+        /// Initial playback speed multiplier
+        #[arg(short, long, default_value_t = 1.0, value_parser = source::parse_speed)]
+        speed: f64,
+    },
 }
 
 fn main() -> io::Result<()> {
@@ -72,6 +158,59 @@ fn main() -> io::Result<()> {
     let update_interval = args.update_interval.unwrap_or(config.update_interval);
     let no_color = args.no_color || config.no_color;
 
+    if let Some(Command::Record { output, interval, format, max_size, keep, samples, top }) = args.command {
+        let interval = Duration::from_millis(interval.unwrap_or(update_interval));
+        // A zero interval would write samples in a busy loop
+        if interval.is_zero() {
+            eprintln!("Error: the sampling interval must be greater than 0");
+            std::process::exit(1);
+        }
+        let mut recorder = Recorder::new(output, format, max_size, keep, top)?;
+        let mut app = App::new();
+        app.apply_config(&config);
+        return record::run(&mut app, &mut recorder, interval, samples);
+    }
+
+    if let Some(Command::Serve { listen, interval }) = args.command {
+        let mut app = App::new();
+        app.apply_config(&config);
+        let interval = Duration::from_millis(interval.unwrap_or(update_interval));
+        // A zero period would make the collector's timer panic
+        if interval.is_zero() {
+            eprintln!("Error: the collection interval must be greater than 0");
+            std::process::exit(1);
+        }
+        let runtime = tokio::runtime::Runtime::new()?;
+        return runtime.block_on(exporter::serve(app, listen, interval));
+    }
+
+    // Replay loads before touching the terminal so errors print normally
+    let replay = match &args.command {
+        Some(Command::Replay { file, .. }) => match ReplaySource::load(file) {
+            Ok(source) => Some(source),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        },
+        _ => None,
+    };
+
+    // Headless snapshot mode
+    if args.once || args.json {
+        return print_snapshot(&config, update_interval, args.top, args.json);
+    }
+
+    // --color wins over --no-color, which wins over detection
+    let color_depth = match args.color {
+        Some(depth) => depth,
+        None if no_color => ColorDepth::None,
+        None => ColorDepth::detect(),
+    };
+
+    // Themes load before touching the terminal so warnings print normally
+    let themes = ThemeSet::load(&config.theme, color_depth);
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -80,9 +219,17 @@ fn main() -> io::Result<()> {
     let mut terminal = Terminal::new(backend)?;
 
     // Create app and run
-    let mut app = App::new();
-    app.no_color = no_color;
+    let mut app = match replay {
+        Some(source) => App::with_source(Box::new(source)),
+        None => App::new(),
+    };
+    app.no_color = color_depth == ColorDepth::None;
+    app.themes = themes;
     app.apply_config(&config);
+    app.load_alerts(&config);
+    if let Some(Command::Replay { speed, .. }) = args.command {
+        app.replay_speed = speed.clamp(0.25, 16.0);
+    }
     let res = run_app(&mut terminal, &mut app, update_interval);
 
     // Restore terminal
@@ -101,17 +248,48 @@ fn main() -> io::Result<()> {
     Ok(())
 }
 
+fn print_snapshot(config: &Config, update_interval: u64, top: usize, json: bool) -> io::Result<()> {
+    let mut app = App::new();
+    app.apply_config(config);
+
+    // Rates (CPU usage, network and disk speeds) need a second sample
+    std::thread::sleep(Duration::from_millis(update_interval).max(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL));
+    app.update();
+
+    let snapshot = Snapshot::new(&app, top);
+    let out = if json {
+        snapshot
+            .to_json()
+            .map(|s| s + "\n")
+            .map_err(io::Error::other)?
+    } else {
+        snapshot.to_text()
+    };
+
+    // A closed pipe (e.g. `mprobe --json | head`) is not an error
+    match io::stdout().write_all(out.as_bytes()) {
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        res => res,
+    }
+}
+
 fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     app: &mut App,
     update_interval: u64,
 ) -> io::Result<()> {
-    let tick_rate = Duration::from_millis(update_interval);
     let mut last_tick = std::time::Instant::now();
 
     loop {
         terminal.draw(|f| ui::draw(f, app))?;
 
+        // Replays advance one sample per tick, scaled by the playback speed
+        let tick_rate = if app.is_replay() {
+            Duration::from_millis(update_interval).div_f64(app.replay_speed)
+        } else {
+            Duration::from_millis(update_interval)
+        };
+
         let timeout = tick_rate.saturating_sub(last_tick.elapsed());
 
         if event::poll(timeout)? {
@@ -144,7 +322,15 @@ fn run_app(
                             return Ok(())
                         }
                         KeyCode::Char('?') => app.toggle_help(),
+                        KeyCode::Char(' ') if app.is_replay() && !app.filter_mode => app.toggle_pause(),
+                        KeyCode::Right if app.is_replay() => app.step_replay(1),
+                        KeyCode::Left if app.is_replay() => app.step_replay(-1),
+                        KeyCode::Char('+') if app.is_replay() && !app.filter_mode => app.change_replay_speed(true),
+                        KeyCode::Char('-') if app.is_replay() && !app.filter_mode => app.change_replay_speed(false),
                         KeyCode::Tab => app.next_tab(),
+                        KeyCode::Char(c @ '1'..='4') if !app.filter_mode => {
+                            app.select_tab(c as usize - '1' as usize)
+                        }
                         KeyCode::BackTab => app.prev_tab(),
                         KeyCode::Up | KeyCode::Char('k') => app.scroll_up(),
                         KeyCode::Down | KeyCode::Char('j') => app.scroll_down(),
@@ -152,7 +338,14 @@ fn run_app(
                         KeyCode::Char('G') => app.scroll_to_bottom(),
                         KeyCode::Char('/') => app.toggle_filter_mode(),
                         KeyCode::Char('t') => app.toggle_tree_view(),
+                        KeyCode::Char('C') if !app.filter_mode => app.toggle_cgroup_view(),
+                        KeyCode::Char('o') if !app.filter_mode => app.toggle_io_only(),
+                        KeyCode::Char('p') if !app.filter_mode => app.toggle_per_core_view(),
+                        KeyCode::Char('m') if !app.filter_mode => app.cycle_used_memory(),
                         KeyCode::Char('c') => app.toggle_connections(),
+                        KeyCode::Char('a') if !app.filter_mode => app.toggle_alerts(),
+                        KeyCode::Char('T') if !app.filter_mode => app.cycle_theme(),
+                        KeyCode::Char('i') if !app.filter_mode => app.cycle_interface(),
                         KeyCode::Char('s') => app.cycle_sort(),
                         KeyCode::Char('r') => app.toggle_sort_order(),
                         KeyCode::Char('x') => app.initiate_kill(KillSignal::Term),
@@ -161,6 +354,7 @@ fn run_app(
                         KeyCode::Char(c) if app.filter_mode => app.add_filter_char(c),
                         KeyCode::Backspace if app.filter_mode => app.remove_filter_char(),
                         KeyCode::Enter if app.filter_mode => app.toggle_filter_mode(),
+                        KeyCode::Enter => app.toggle_expanded(),
                         _ => {}
                     }
                 }
@@ -188,12 +382,11 @@ fn run_app(
                             }
                         }
                         MouseEventKind::Down(_) => {
-                            // Click to select process in the process list area
-                            // Process list starts after header (5) + top row (11) + bottom row (9) = 25 lines
-                            // Plus 1 for the table header row
-                            let process_area_start = 26u16;
-                            if mouse.row >= process_area_start {
-                                let clicked_row = (mouse.row - process_area_start) as usize;
+                            // Click to select process in the process list area,
+                            // wherever the current tab and layout put it
+                            let screen = Rect::from((Position::ORIGIN, terminal.size()?));
+                            let position = Position::new(mouse.column, mouse.row);
+                            if let Some(clicked_row) = ui::process_row_at(app, screen, position) {
                                 if clicked_row < app.process_data.processes.len() {
                                     app.process_scroll = clicked_row;
                                 }
@@ -207,7 +400,14 @@ fn run_app(
         }
 
         if last_tick.elapsed() >= tick_rate {
-            app.update();
+            if !app.paused {
+                app.update();
+            }
+            if app.alerts.take_bell() {
+                let mut stdout = io::stdout();
+                stdout.write_all(b"\x07")?;
+                stdout.flush()?;
+            }
             app.clear_expired_status();
             last_tick = std::time::Instant::now();
         }
@@ -0,0 +1,178 @@
+use crate::app::App;
+use crate::snapshot::Snapshot;
+use clap::ValueEnum;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum RecordFormat {
+    /// One JSON snapshot per line
+    Ndjson,
+    /// One row of headline metrics per line
+    Csv,
+}
+
+const CSV_HEADER: &str = "timestamp,cpu_percent,mem_used,mem_total,mem_percent,swap_used,swap_percent,\
+net_down,net_up,disk_read,disk_write,load_1,load_5,load_15,processes,running";
+
+/// Appends samples to a file, rotating it once it grows past a size limit
+pub struct Recorder {
+    path: PathBuf,
+    format: RecordFormat,
+    max_size: Option<u64>,
+    keep: usize,
+    top: usize,
+    file: File,
+    written: u64,
+}
+
+impl Recorder {
+    pub fn new(
+        path: PathBuf,
+        format: RecordFormat,
+        max_size: Option<u64>,
+        keep: usize,
+        top: usize,
+    ) -> io::Result<Self> {
+        let (file, written) = open_append(&path)?;
+        let mut recorder = Self {
+            path,
+            format,
+            max_size,
+            keep,
+            top,
+            file,
+            written,
+        };
+        recorder.write_header()?;
+        Ok(recorder)
+    }
+
+    /// Write one sample taken from the app's current state
+    pub fn write_sample(&mut self, app: &App) -> io::Result<()> {
+        let snapshot = Snapshot::new(app, self.top);
+        let line = match self.format {
+            RecordFormat::Ndjson => serde_json::to_string(&snapshot).map_err(io::Error::other)?,
+            RecordFormat::Csv => csv_row(&snapshot),
+        };
+
+        if let Some(max_size) = self.max_size {
+            if self.written > 0 && self.written + line.len() as u64 + 1 > max_size {
+                self.rotate()?;
+            }
+        }
+
+        self.write_line(&line)
+    }
+
+    fn write_line(&mut self, line: &str) -> io::Result<()> {
+        self.file.write_all(line.as_bytes())?;
+        self.file.write_all(b"\n")?;
+        self.file.flush()?;
+        self.written += line.len() as u64 + 1;
+        Ok(())
+    }
+
+    fn write_header(&mut self) -> io::Result<()> {
+        if self.format == RecordFormat::Csv && self.written == 0 {
+            self.write_line(CSV_HEADER)?;
+        }
+        Ok(())
+    }
+
+    /// Shift metrics.ndjson -> metrics.ndjson.1 -> .2 ..., dropping the oldest
+    fn rotate(&mut self) -> io::Result<()> {
+        if self.keep == 0 {
+            fs::remove_file(&self.path)?;
+        } else {
+            let _ = fs::remove_file(rotated_path(&self.path, self.keep));
+            for i in (1..self.keep).rev() {
+                let from = rotated_path(&self.path, i);
+                if from.exists() {
+                    fs::rename(&from, rotated_path(&self.path, i + 1))?;
+                }
+            }
+            fs::rename(&self.path, rotated_path(&self.path, 1))?;
+        }
+
+        let (file, written) = open_append(&self.path)?;
+        self.file = file;
+        self.written = written;
+        self.write_header()
+    }
+}
+
+/// Sample the collectors every `interval` and append them to the recorder
+pub fn run(app: &mut App, recorder: &mut Recorder, interval: Duration, samples: Option<u64>) -> io::Result<()> {
+    let mut taken = 0u64;
+    // Deadlines keep a steady cadence regardless of how long sampling takes
+    let mut next = Instant::now() + interval;
+
+    loop {
+        thread::sleep(next.saturating_duration_since(Instant::now()));
+        next += interval;
+
+        app.update();
+        recorder.write_sample(app)?;
+
+        taken += 1;
+        if samples.is_some_and(|n| taken >= n) {
+            return Ok(());
+        }
+    }
+}
+
+/// Parse a size like "500K", "10M" or "1G" (binary units) into bytes
+pub fn parse_size(s: &str) -> Result<u64, String> {
+    let s = s.trim();
+    let (digits, multiplier) = match s.chars().last().map(|c| c.to_ascii_uppercase()) {
+        Some('K') => (&s[..s.len() - 1], 1024),
+        Some('M') => (&s[..s.len() - 1], 1024 * 1024),
+        Some('G') => (&s[..s.len() - 1], 1024 * 1024 * 1024),
+        _ => (s, 1),
+    };
+
+    let n = digits
+        .trim()
+        .parse::<u64>()
+        .map_err(|_| format!("invalid size '{}' (expected e.g. 500K, 10M, 1G)", s))?;
+    n.checked_mul(multiplier)
+        .ok_or_else(|| format!("size '{}' is too large", s))
+}
+
+fn open_append(path: &Path) -> io::Result<(File, u64)> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    let written = file.metadata()?.len();
+    Ok((file, written))
+}
+
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+fn csv_row(snapshot: &Snapshot) -> String {
+    format!(
+        "{},{:.2},{},{},{:.2},{},{:.2},{},{},{},{},{:.2},{:.2},{:.2},{},{}",
+        snapshot.timestamp,
+        snapshot.cpu.total_usage,
+        snapshot.memory.used,
+        snapshot.memory.total,
+        snapshot.memory.used_percent,
+        snapshot.memory.swap_used,
+        snapshot.memory.swap_percent,
+        snapshot.network.speed_down,
+        snapshot.network.speed_up,
+        snapshot.disks.read_rate,
+        snapshot.disks.write_rate,
+        snapshot.load_avg[0],
+        snapshot.load_avg[1],
+        snapshot.load_avg[2],
+        snapshot.total_processes,
+        snapshot.running_processes,
+    )
+}
@@ -0,0 +1,138 @@
+use crate::app::App;
+use crate::data::{
+    BatteryData, CgroupData, ConnectionData, CpuData, DiskData, MemoryData, NetworkData, PressureData,
+    ProcessData, ProcessInfo, TemperatureData,
+};
+use serde::Serialize;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A point-in-time view of everything the UI shows, for headless output
+#[derive(Serialize)]
+pub struct Snapshot<'a> {
+    pub timestamp: u64, // seconds since the Unix epoch
+    pub hostname: &'a str,
+    pub os_name: &'a str,
+    pub kernel_version: &'a str,
+    pub uptime: u64,
+    pub load_avg: [f64; 3],
+    pub cpu: &'a CpuData,
+    pub memory: &'a MemoryData,
+    pub disks: &'a DiskData,
+    pub network: &'a NetworkData,
+    pub temperatures: &'a TemperatureData,
+    pub pressure: &'a PressureData,
+    pub cgroup: &'a CgroupData,
+    pub batteries: &'a BatteryData,
+    pub connections: &'a ConnectionData,
+    pub total_processes: usize,
+    pub running_processes: usize,
+    pub processes: &'a [ProcessInfo], // top processes, in the current sort order
+}
+
+impl<'a> Snapshot<'a> {
+    pub fn new(app: &'a App, top: usize) -> Self {
+        let processes = &app.process_data.processes;
+        let (l1, l5, l15) = app.load_avg;
+
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            hostname: &app.hostname,
+            os_name: &app.os_name,
+            kernel_version: &app.kernel_version,
+            uptime: app.uptime,
+            load_avg: [l1, l5, l15],
+            cpu: &app.cpu_data,
+            memory: &app.memory_data,
+            disks: &app.disk_data,
+            network: &app.network_data,
+            temperatures: &app.temperature_data,
+            pressure: &app.pressure_data,
+            cgroup: &app.cgroup_data,
+            batteries: &app.battery_data,
+            connections: &app.connection_data,
+            total_processes: app.process_data.total_processes,
+            running_processes: app.process_data.running_processes,
+            processes: &processes[..top.min(processes.len())],
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| format!("Failed to serialize snapshot: {}", e))
+    }
+
+    /// Render a short human-readable summary
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+
+        out.push_str(&format!(
+            "{} ({} {}), up {}s, load {:.2} {:.2} {:.2}\n",
+            self.hostname,
+            self.os_name,
+            self.kernel_version,
+            self.uptime,
+            self.load_avg[0],
+            self.load_avg[1],
+            self.load_avg[2]
+        ));
+        out.push_str(&format!(
+            "CPU  {:5.1}%  {} cores\n",
+            self.cpu.total_usage, self.cpu.core_count
+        ));
+        out.push_str(&format!(
+            "MEM  {:5.1}%  {} / {}\n",
+            self.memory.used_percent,
+            MemoryData::format_bytes(self.memory.used),
+            MemoryData::format_bytes(self.memory.total)
+        ));
+        out.push_str(&format!(
+            "SWP  {:5.1}%  {} / {}\n",
+            self.memory.swap_percent,
+            MemoryData::format_bytes(self.memory.swap_used),
+            MemoryData::format_bytes(self.memory.swap_total)
+        ));
+        out.push_str(&format!(
+            "NET  down {}/s  up {}/s\n",
+            MemoryData::format_bytes(self.network.speed_down),
+            MemoryData::format_bytes(self.network.speed_up)
+        ));
+        out.push_str(&format!(
+            "DISK read {}/s  write {}/s\n",
+            MemoryData::format_bytes(self.disks.read_rate),
+            MemoryData::format_bytes(self.disks.write_rate)
+        ));
+        for disk in &self.disks.disk_info {
+            out.push_str(&format!(
+                "     {:20} {:5.1}%  {} / {}\n",
+                disk.mount_point,
+                disk.used_percent,
+                DiskData::format_bytes(disk.used),
+                DiskData::format_bytes(disk.total)
+            ));
+        }
+        for sensor in &self.temperatures.sensors {
+            out.push_str(&format!("TEMP {:20} {:.0}°C\n", sensor.label, sensor.temperature));
+        }
+        for battery in &self.batteries.batteries {
+            out.push_str(&format!("BATT {:.0}% {}\n", battery.percentage, battery.state));
+        }
+
+        out.push_str(&format!(
+            "\n{:>7}  {:25} {:>6} {:>8}\n",
+            "PID", "NAME", "CPU%", "MEMORY"
+        ));
+        for proc in self.processes {
+            out.push_str(&format!(
+                "{:>7}  {:25} {:>6.1} {:>8}\n",
+                proc.pid,
+                proc.name,
+                proc.cpu_usage,
+                ProcessData::format_memory(proc.memory)
+            ));
+        }
+
+        out
+    }
+}
@@ -0,0 +1,204 @@
+use crate::app::{App, GRAPH_HISTORY_SIZE};
+use crate::data::{
+    BatteryData, CgroupData, ConnectionData, CpuData, DiskData, MemoryData, NetworkData, PressureData,
+    ProcessInfo, ProcessOptions, TemperatureData,
+};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use sysinfo::System;
+
+/// Where the app's data comes from on each update
+pub trait DataSource: Send {
+    /// Load the next sample into the app's data; returns false if nothing changed
+    fn refresh(&mut self, app: &mut App) -> bool;
+
+    /// Current sample and total sample count, for recorded sources
+    fn position(&self) -> Option<(usize, usize)> {
+        None
+    }
+
+    /// Jump to a sample of a recording, rebuilding graph history up to it
+    fn seek(&mut self, _app: &mut App, _index: usize) {}
+}
+
+/// Collects fresh data from the running system
+pub struct LiveSource;
+
+impl DataSource for LiveSource {
+    fn refresh(&mut self, app: &mut App) -> bool {
+        app.system.refresh_all();
+        app.uptime = System::uptime();
+
+        // Update load average
+        let load = System::load_average();
+        app.load_avg = (load.one, load.five, load.fifteen);
+
+        app.cgroup_data.update();
+        app.cpu_data.update(&app.system);
+        app.cpu_data.apply_cgroup(&app.cgroup_data);
+        app.memory_data.update(&app.system);
+        app.memory_data.apply_cgroup(&app.cgroup_data);
+        app.disk_data.update();
+        app.temperature_data.update();
+        app.pressure_data.update();
+        app.battery_data.update();
+        app.network_data.update(&app.system);
+
+        // Update Connection data (less frequently - every update is fine)
+        app.connection_data.update();
+
+        // Update per-process network usage (uses socket owners from the connection scan)
+        app.process_net_data.update(&app.connection_data);
+
+        // Update Process data
+        let options = ProcessOptions {
+            filter: &app.filter_text,
+            sort_column: app.sort_column,
+            sort_ascending: app.sort_ascending,
+            view: app.process_view,
+            io_only: app.io_only,
+            expanded_cgroups: &app.expanded_cgroups,
+            collapsed_pids: &app.collapsed_pids,
+        };
+        app.process_data.update(&app.system, &options, &app.process_net_data);
+
+        true
+    }
+}
+
+/// One line of a recording made with `mprobe record` (NDJSON format)
+#[derive(Deserialize)]
+struct Sample {
+    hostname: String,
+    os_name: String,
+    kernel_version: String,
+    uptime: u64,
+    load_avg: [f64; 3],
+    cpu: CpuData,
+    memory: MemoryData,
+    disks: DiskData,
+    network: NetworkData,
+    temperatures: TemperatureData,
+    // Missing from recordings made before PSI support
+    #[serde(default)]
+    pressure: PressureData,
+    #[serde(default)]
+    cgroup: CgroupData,
+    batteries: BatteryData,
+    connections: ConnectionData,
+    total_processes: usize,
+    running_processes: usize,
+    processes: Vec<ProcessInfo>,
+}
+
+/// Plays back a recorded session, one sample per update
+pub struct ReplaySource {
+    // Raw NDJSON lines, validated at load and parsed again when applied
+    samples: Vec<String>,
+    next: usize,
+}
+
+impl ReplaySource {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+        let mut samples = Vec::new();
+        for (i, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            serde_json::from_str::<Sample>(line)
+                .map_err(|e| format!("{}:{}: invalid sample: {}", path.display(), i + 1, e))?;
+            samples.push(line.to_string());
+        }
+
+        if samples.is_empty() {
+            return Err(format!("{} contains no samples", path.display()));
+        }
+
+        Ok(Self { samples, next: 0 })
+    }
+
+    fn apply(&self, app: &mut App, index: usize) {
+        // Lines were validated in load(), so this cannot fail
+        let Ok(sample) = serde_json::from_str::<Sample>(&self.samples[index]) else {
+            return;
+        };
+
+        app.hostname = sample.hostname;
+        app.os_name = sample.os_name;
+        app.kernel_version = sample.kernel_version;
+        app.uptime = sample.uptime;
+        app.load_avg = (sample.load_avg[0], sample.load_avg[1], sample.load_avg[2]);
+
+        app.cpu_data = sample.cpu;
+        app.memory_data = sample.memory;
+        app.disk_data = sample.disks;
+        app.network_data = sample.network;
+        app.temperature_data = sample.temperatures;
+        app.pressure_data = sample.pressure;
+        app.cgroup_data = sample.cgroup;
+        app.battery_data = sample.batteries;
+        app.connection_data = sample.connections;
+
+        let options = ProcessOptions {
+            filter: &app.filter_text,
+            sort_column: app.sort_column,
+            sort_ascending: app.sort_ascending,
+            view: app.process_view,
+            io_only: app.io_only,
+            expanded_cgroups: &app.expanded_cgroups,
+            collapsed_pids: &app.collapsed_pids,
+        };
+        app.process_data.set_recorded(
+            sample.processes,
+            sample.total_processes,
+            sample.running_processes,
+            &options,
+        );
+    }
+}
+
+impl DataSource for ReplaySource {
+    fn refresh(&mut self, app: &mut App) -> bool {
+        if self.next >= self.samples.len() {
+            return false;
+        }
+
+        self.apply(app, self.next);
+        self.next += 1;
+        true
+    }
+
+    fn position(&self) -> Option<(usize, usize)> {
+        Some((self.next.max(1), self.samples.len()))
+    }
+
+    fn seek(&mut self, app: &mut App, index: usize) {
+        let index = index.min(self.samples.len() - 1);
+
+        // Replay the samples leading up to the target so graphs look the same
+        // as they did when playing through
+        app.reset_history();
+        for i in index.saturating_sub(GRAPH_HISTORY_SIZE - 1)..=index {
+            self.apply(app, i);
+            app.record_history();
+        }
+
+        self.next = index + 1;
+    }
+}
+
+/// Parse a playback speed multiplier; must be a finite number above 0
+pub fn parse_speed(s: &str) -> Result<f64, String> {
+    let speed = s
+        .trim()
+        .parse::<f64>()
+        .map_err(|_| format!("invalid speed '{}' (expected a number like 0.5 or 2)", s))?;
+    if !speed.is_finite() || speed <= 0.0 {
+        return Err(format!("speed '{}' must be a finite number greater than 0", s));
+    }
+    Ok(speed)
+}
@@ -0,0 +1,133 @@
+use crate::app::App;
+use crate::ui::Theme;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    Frame,
+};
+
+pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
+    let alerts = &app.alerts;
+    let firing = alerts.firing_count();
+
+    let block = Block::default()
+        .title(Line::from(vec![
+            Span::styled(" ", Style::default()),
+            Span::styled(
+                "ALERTS",
+                Style::default()
+                    .fg(theme.usage_critical)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(" ({} firing / {} rules) ", firing, alerts.rules.len()),
+                Style::default().fg(theme.fg_muted),
+            ),
+        ]))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border))
+        .style(Style::default().bg(theme.bg_secondary));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    if alerts.rules.is_empty() {
+        let hint = Paragraph::new(vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                "  No alerts configured. Add [[alerts]] tables to the config file.",
+                Style::default().fg(theme.fg_muted),
+            )),
+        ]);
+        frame.render_widget(hint, inner);
+        return;
+    }
+
+    // Rules on top, event log below
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(alerts.rules.len() as u16 + 2),
+            Constraint::Min(1),
+        ])
+        .split(inner);
+
+    let header_cells = ["STATE", "NAME", "CONDITION", "VALUE"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().fg(theme.table_header)));
+    let header = Row::new(header_cells)
+        .style(Style::default().bg(theme.bg_secondary))
+        .height(1);
+
+    let rows = alerts.rules.iter().map(|state| {
+        let (label, color) = if state.firing {
+            ("FIRING", theme.usage_critical)
+        } else {
+            ("ok", theme.success)
+        };
+        let value = state
+            .value
+            .map(|v| state.metric.format(v))
+            .unwrap_or_else(|| "-".to_string());
+
+        Row::new(vec![
+            Cell::from(label).style(Style::default().fg(color).add_modifier(Modifier::BOLD)),
+            Cell::from(state.label()).style(Style::default().fg(theme.fg)),
+            Cell::from(state.condition()).style(Style::default().fg(theme.fg_dim)),
+            Cell::from(value).style(Style::default().fg(theme.fg)),
+        ])
+    });
+
+    let widths = [
+        Constraint::Length(8),   // State
+        Constraint::Min(16),     // Name
+        Constraint::Min(20),     // Condition
+        Constraint::Length(12),  // Value
+    ];
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .style(Style::default().bg(theme.bg_secondary));
+    frame.render_widget(table, chunks[0]);
+
+    let lines: Vec<Line> = if alerts.log.is_empty() {
+        vec![Line::from(Span::styled(
+            "No alerts have fired yet",
+            Style::default().fg(theme.fg_muted),
+        ))]
+    } else {
+        alerts
+            .log
+            .iter()
+            .take(chunks[1].height as usize)
+            .map(|event| {
+                let (marker, color) = if event.firing {
+                    ("▲", theme.usage_critical)
+                } else {
+                    ("▼", theme.success)
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!("{:>8} ", format_age(event.time.elapsed().as_secs())),
+                        Style::default().fg(theme.fg_muted),
+                    ),
+                    Span::styled(format!("{} ", marker), Style::default().fg(color)),
+                    Span::styled(&event.message, Style::default().fg(theme.fg)),
+                ])
+            })
+            .collect()
+    };
+    frame.render_widget(Paragraph::new(lines), chunks[1]);
+}
+
+fn format_age(secs: u64) -> String {
+    if secs < 60 {
+        format!("{}s ago", secs)
+    } else if secs < 3600 {
+        format!("{}m ago", secs / 60)
+    } else {
+        format!("{}h ago", secs / 3600)
+    }
+}
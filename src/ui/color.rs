@@ -0,0 +1,199 @@
+use clap::ValueEnum;
+use ratatui::style::Color;
+use std::path::PathBuf;
+
+/// How many colors the terminal can show
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ColorDepth {
+    #[value(name = "truecolor")]
+    TrueColor,
+    #[value(name = "256")]
+    Ansi256,
+    #[value(name = "16")]
+    Ansi16,
+    #[value(name = "none")]
+    None,
+}
+
+impl ColorDepth {
+    /// Guess the terminal's color support from COLORTERM, TERM and its terminfo entry
+    pub fn detect() -> Self {
+        let colorterm = std::env::var("COLORTERM").unwrap_or_default().to_lowercase();
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            return ColorDepth::TrueColor;
+        }
+
+        let term = std::env::var("TERM").unwrap_or_default();
+        if term.is_empty() || term == "dumb" {
+            return ColorDepth::None;
+        }
+
+        match terminfo_max_colors(&term) {
+            Some(n) if n >= 1 << 24 => ColorDepth::TrueColor,
+            Some(n) if n >= 256 => ColorDepth::Ansi256,
+            Some(n) if n >= 8 => ColorDepth::Ansi16,
+            Some(_) => ColorDepth::None,
+            // No terminfo entry, so go by the name alone
+            None if term.ends_with("-direct") || term.contains("truecolor") => ColorDepth::TrueColor,
+            None if term.contains("256color") => ColorDepth::Ansi256,
+            None => ColorDepth::Ansi16,
+        }
+    }
+
+    /// Map a color to the closest one this depth can show
+    pub fn quantize(self, color: Color) -> Color {
+        match (self, color) {
+            (ColorDepth::TrueColor | ColorDepth::None, _) => color,
+            (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_256(r, g, b)),
+            (ColorDepth::Ansi16, Color::Rgb(r, g, b)) => nearest_16(r, g, b),
+            (ColorDepth::Ansi16, Color::Indexed(i)) if i >= 16 => {
+                let (r, g, b) = indexed_to_rgb(i);
+                nearest_16(r, g, b)
+            }
+            _ => color,
+        }
+    }
+}
+
+/// Closest entry in the xterm 256-color palette (the 6x6x6 cube or the gray ramp)
+fn nearest_256(r: u8, g: u8, b: u8) -> u8 {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    let level = |v: u8| match v {
+        0..=47 => 0,
+        48..=114 => 1,
+        _ => (v as usize - 35) / 40,
+    };
+
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = (LEVELS[ri], LEVELS[gi], LEVELS[bi]);
+    let cube_index = 16 + 36 * ri + 6 * gi + bi;
+
+    // Gray ramp 232..=255 covers 8..=238 in steps of 10
+    let average = (r as usize + g as usize + b as usize) / 3;
+    let gray_step = (average.saturating_sub(3) / 10).min(23);
+    let gray = 8 + 10 * gray_step as u8;
+    let gray_index = 232 + gray_step;
+
+    if distance((r, g, b), (gray, gray, gray)) < distance((r, g, b), cube) {
+        gray_index as u8
+    } else {
+        cube_index as u8
+    }
+}
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> i32 {
+    let d = |x: u8, y: u8| (x as i32 - y as i32).pow(2);
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+/// Closest ANSI color by hue and lightness, since the 16-color palette itself
+/// differs from terminal to terminal
+fn nearest_16(r: u8, g: u8, b: u8) -> Color {
+    let (rf, gf, bf) = (r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0);
+    let max = rf.max(gf).max(bf);
+    let min = rf.min(gf).min(bf);
+    let lightness = (max + min) / 2.0;
+    let chroma = max - min;
+
+    // Grays
+    if chroma < 0.15 {
+        return match lightness {
+            l if l < 0.2 => Color::Black,
+            l if l < 0.55 => Color::DarkGray,
+            l if l < 0.85 => Color::Gray,
+            _ => Color::White,
+        };
+    }
+
+    let hue = if max == rf {
+        60.0 * ((gf - bf) / chroma).rem_euclid(6.0)
+    } else if max == gf {
+        60.0 * ((bf - rf) / chroma + 2.0)
+    } else {
+        60.0 * ((rf - gf) / chroma + 4.0)
+    };
+
+    let light = lightness > 0.6;
+    match hue {
+        h if !(30.0..330.0).contains(&h) => if light { Color::LightRed } else { Color::Red },
+        h if h < 90.0 => if light { Color::LightYellow } else { Color::Yellow },
+        h if h < 150.0 => if light { Color::LightGreen } else { Color::Green },
+        h if h < 210.0 => if light { Color::LightCyan } else { Color::Cyan },
+        h if h < 270.0 => if light { Color::LightBlue } else { Color::Blue },
+        _ => if light { Color::LightMagenta } else { Color::Magenta },
+    }
+}
+
+/// RGB value of an xterm 256-color palette entry above the first 16
+fn indexed_to_rgb(index: u8) -> (u8, u8, u8) {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    if index >= 232 {
+        let gray = 8 + 10 * (index - 232);
+        return (gray, gray, gray);
+    }
+
+    let i = (index - 16) as usize;
+    (LEVELS[i / 36], LEVELS[(i / 6) % 6], LEVELS[i % 6])
+}
+
+/// Read max_colors from the compiled terminfo entry for `term`
+fn terminfo_max_colors(term: &str) -> Option<i64> {
+    let first = term.chars().next()?;
+
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    if let Some(dir) = std::env::var_os("TERMINFO") {
+        dirs.push(dir.into());
+    }
+    if let Some(home) = dirs::home_dir() {
+        dirs.push(home.join(".terminfo"));
+    }
+    if let Ok(list) = std::env::var("TERMINFO_DIRS") {
+        dirs.extend(list.split(':').filter(|d| !d.is_empty()).map(PathBuf::from));
+    }
+    dirs.extend(
+        ["/etc/terminfo", "/lib/terminfo", "/usr/share/terminfo", "/usr/lib/terminfo"]
+            .iter()
+            .map(PathBuf::from),
+    );
+
+    // Entries live under their first letter, or its hex code on macOS
+    let subdirs = [first.to_string(), format!("{:x}", first as u32)];
+    dirs.iter()
+        .flat_map(|dir| subdirs.iter().map(move |sub| dir.join(sub).join(term)))
+        .find_map(|path| std::fs::read(path).ok())
+        .and_then(|data| parse_max_colors(&data))
+}
+
+/// Extract max_colors from a compiled terminfo file (see term(5))
+fn parse_max_colors(data: &[u8]) -> Option<i64> {
+    const MAX_COLORS: usize = 13;  // Index in the numbers section
+
+    let word = |i: usize| -> Option<usize> {
+        Some(u16::from_le_bytes([*data.get(i)?, *data.get(i + 1)?]) as usize)
+    };
+
+    // The extended format stores numbers as 32-bit values
+    let number_size = match word(0)? {
+        0o432 => 2,
+        0o1036 => 4,
+        _ => return None,
+    };
+    let (names_size, bools, numbers) = (word(2)?, word(4)?, word(6)?);
+    if numbers <= MAX_COLORS {
+        return None;
+    }
+
+    // Numbers start on an even offset after the names and booleans
+    let mut offset = 12 + names_size + bools;
+    offset += offset % 2;
+    let at = offset + MAX_COLORS * number_size;
+
+    let value = if number_size == 2 {
+        i16::from_le_bytes([*data.get(at)?, *data.get(at + 1)?]) as i64
+    } else {
+        i32::from_le_bytes(data.get(at..at + 4)?.try_into().ok()?) as i64
+    };
+
+    // Negative means the capability is absent
+    (value >= 0).then_some(value)
+}
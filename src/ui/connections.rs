@@ -31,7 +31,7 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
         .style(Style::default().bg(theme.bg_secondary));
 
     // Header
-    let header_cells = ["PROTO", "LOCAL", "REMOTE", "STATE"]
+    let header_cells = ["PROTO", "LOCAL", "REMOTE", "STATE", "PROCESS"]
         .iter()
         .map(|h| Cell::from(*h).style(Style::default().fg(theme.table_header)));
 
@@ -66,6 +66,8 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
             Cell::from(truncate_addr(&conn.local_addr, 20)).style(Style::default().fg(theme.fg)),
             Cell::from(truncate_addr(&conn.remote_addr, 20)).style(Style::default().fg(theme.fg_dim)),
             Cell::from(format_state(&conn.state)).style(Style::default().fg(state_color)),
+            Cell::from(format_owner(conn.pid, conn.process_name.as_deref()))
+                .style(Style::default().fg(theme.fg_dim)),
         ];
 
         Row::new(cells)
@@ -76,6 +78,7 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
         Constraint::Min(15),     // Local
         Constraint::Min(15),     // Remote
         Constraint::Length(12),  // State
+        Constraint::Min(16),     // Process
     ];
 
     let table = Table::new(rows, widths)
@@ -94,6 +97,14 @@ fn truncate_addr(addr: &str, max_len: usize) -> String {
     }
 }
 
+fn format_owner(pid: Option<u32>, name: Option<&str>) -> String {
+    match (pid, name) {
+        (Some(pid), Some(name)) if !name.is_empty() => format!("{} ({})", name, pid),
+        (Some(pid), _) => pid.to_string(),
+        _ => "-".to_string(),
+    }
+}
+
 fn format_state(state: &str) -> String {
     match state {
         "ESTABLISHED" => "ESTAB".to_string(),
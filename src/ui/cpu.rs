@@ -1,9 +1,9 @@
 use crate::app::App;
-use crate::data::CpuData;
+use crate::data::{CpuData, CpuTimes};
 use crate::ui::Theme;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Modifier, Style},
+    style::{Color, Modifier, Style},
     symbols::Marker,
     text::{Line, Span},
     widgets::{Axis, Block, Borders, Chart, Dataset, GraphType},
@@ -62,7 +62,7 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
         ),
         Span::styled(" │ ", Style::default().fg(theme.border)),
         Span::styled(
-            format!("{}c", app.cpu_data.core_count),
+            app.cpu_data.format_cores(),
             Style::default().fg(theme.fg_dim),
         ),
         Span::styled(" │ ", Style::default().fg(theme.border)),
@@ -76,6 +76,23 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
         chunks[0],
     );
 
+    // Per-core grid takes the place of the graph and bar
+    if app.per_core_view {
+        let grid_area = Rect {
+            height: chunks[1].height + chunks[2].height,
+            ..chunks[1]
+        };
+        draw_core_grid(frame, app, grid_area, theme);
+        return;
+    }
+
+    // Split by CPU state where the kernel reports it
+    if let Some(times) = &app.cpu_data.times {
+        draw_stacked_graph(frame, app, chunks[1], theme);
+        draw_stacked_bar(frame, times, chunks[2], theme);
+        return;
+    }
+
     // Graph
     let data: Vec<(f64, f64)> = app
         .cpu_history
@@ -125,3 +142,199 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
         chunks[2],
     );
 }
+
+/// Legend labels for each state in CpuTimes::parts order
+const TIME_LABELS: [&str; 7] = ["usr", "nice", "sys", "irq", "sirq", "io", "steal"];
+
+/// Colors for each state in CpuTimes::parts order
+fn time_colors(theme: &Theme) -> [Color; 7] {
+    [
+        theme.cpu_color,      // usr
+        theme.usage_low,      // nice
+        theme.mem_color,      // sys
+        theme.disk_write,     // irq
+        theme.swap_color,     // sirq
+        theme.warning,        // io
+        theme.usage_critical, // steal
+    ]
+}
+
+/// Stacked area of CPU time by state, newest sample on the right. Each cell
+/// holds two levels using a half block (lower half fg, upper half bg).
+fn draw_stacked_graph(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
+    let colors = time_colors(theme);
+    let history = &app.cpu_times_history;
+    let width = area.width as usize;
+    let levels = area.height as usize * 2;
+    let offset = width.saturating_sub(history.len());
+    let buf = frame.buffer_mut();
+
+    for (column, times) in history.iter().skip(history.len().saturating_sub(width)).enumerate() {
+        let mut top = 0.0;
+        let bounds = times.parts().map(|(_, value)| {
+            top += value;
+            top
+        });
+
+        // State covering the middle of a level, if the stack reaches it
+        let color_at = |level: usize| {
+            let height = (level as f64 + 0.5) / levels as f64 * 100.0;
+            bounds.iter().position(|&top| height < top).map(|i| colors[i])
+        };
+
+        let x = area.x + (offset + column) as u16;
+        for row in 0..area.height {
+            let level = row as usize * 2;
+            let Some(lower) = color_at(level) else {
+                break;
+            };
+            let upper = color_at(level + 1).unwrap_or(theme.bg_secondary);
+
+            if let Some(cell) = buf.cell_mut((x, area.bottom() - 1 - row)) {
+                cell.set_char('▄').set_fg(lower).set_bg(upper);
+            }
+        }
+    }
+}
+
+/// Bar split by CPU state, with a legend below it
+fn draw_stacked_bar(frame: &mut Frame, times: &CpuTimes, area: Rect, theme: &Theme) {
+    let colors = time_colors(theme);
+    let bar_width = area.width.saturating_sub(2) as usize;
+
+    let mut bar = Vec::new();
+    let mut cumulative = 0.0;
+    let mut filled = 0;
+    for ((_, value), color) in times.parts().iter().zip(colors) {
+        // Round the running total so segments never add up past the bar
+        cumulative += value;
+        let end = ((cumulative / 100.0).clamp(0.0, 1.0) * bar_width as f64).round() as usize;
+        bar.push(Span::styled("▓".repeat(end.saturating_sub(filled)), Style::default().fg(color)));
+        filled = filled.max(end);
+    }
+    bar.push(Span::styled("░".repeat(bar_width - filled), Style::default().fg(theme.border)));
+
+    // The rarer states only when they show up at all
+    let mut legend = Vec::new();
+    for (((name, value), label), color) in times.parts().iter().zip(TIME_LABELS).zip(colors) {
+        if matches!(*name, "nice" | "irq" | "softirq") && *value < 0.1 {
+            continue;
+        }
+        legend.push(Span::styled("■ ", Style::default().fg(color)));
+        legend.push(Span::styled(format!("{} {:.1}%  ", label, value), Style::default().fg(theme.fg_dim)));
+    }
+
+    frame.render_widget(
+        ratatui::widgets::Paragraph::new(vec![Line::from(bar), Line::from(legend)]),
+        area,
+    );
+}
+
+const SPARK_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// One cell per core, filled column by column; falls back to a single glyph
+/// per core when the cells would not fit (e.g. 64+ cores in a small panel)
+fn draw_core_grid(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
+    let usage = &app.cpu_data.per_core_usage;
+    if usage.is_empty() || area.height == 0 || area.width == 0 {
+        return;
+    }
+
+    let rows = (area.height as usize).min(usage.len());
+    let columns = usage.len().div_ceil(rows);
+    let cell_width = area.width as usize / columns;
+    let label_width = (usage.len() - 1).to_string().len();
+
+    // Label, a space and the percentage, plus a gap and a few sparkline cells
+    let min_cell = label_width + 1 + 4 + 1 + 3;
+    if cell_width < min_cell {
+        draw_core_heatmap(frame, app, area, theme);
+        return;
+    }
+
+    let lines: Vec<Line> = (0..rows)
+        .map(|row| {
+            let mut spans = Vec::new();
+            for column in 0..columns {
+                let core = column * rows + row;
+                if core < usage.len() {
+                    spans.extend(core_cell(app, core, cell_width, label_width, theme));
+                }
+            }
+            Line::from(spans)
+        })
+        .collect();
+
+    frame.render_widget(ratatui::widgets::Paragraph::new(lines), area);
+}
+
+/// "12 ▂▃▅▇ 45% 2.1G", dropping the frequency when the cell is narrow
+fn core_cell(app: &App, core: usize, width: usize, label_width: usize, theme: &Theme) -> Vec<Span<'static>> {
+    let usage = app.cpu_data.per_core_usage[core];
+    let color = theme.get_usage_color(usage);
+    let freq = app
+        .cpu_data
+        .per_core_freq
+        .get(core)
+        .map(|&mhz| format_short_frequency(mhz))
+        .unwrap_or_default();
+
+    // Fixed parts: label + space, space + percentage, trailing gap
+    let fixed = label_width + 1 + 1 + 4 + 1;
+    let show_freq = width >= fixed + freq.len() + 1 + 6;
+    let spark_width = width - fixed - if show_freq { freq.len() + 1 } else { 0 };
+
+    let history = app.core_history.get(core);
+    let spark: String = history
+        .map(|h| {
+            h.iter()
+                .skip(h.len().saturating_sub(spark_width))
+                .map(|&v| SPARK_CHARS[((v / 100.0).clamp(0.0, 1.0) * 7.0).round() as usize])
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut spans = vec![
+        Span::styled(format!("{:>width$} ", core, width = label_width), Style::default().fg(theme.fg_muted)),
+        Span::styled(format!("{:<width$}", spark, width = spark_width), Style::default().fg(color)),
+        Span::styled(format!(" {:>3.0}%", usage), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+    ];
+    if show_freq {
+        spans.push(Span::styled(format!(" {}", freq), Style::default().fg(theme.fg_muted)));
+    }
+    spans.push(Span::raw(" "));
+    spans
+}
+
+/// One block glyph per core, wrapping across rows
+fn draw_core_heatmap(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
+    let width = area.width as usize;
+
+    let lines: Vec<Line> = app
+        .cpu_data
+        .per_core_usage
+        .chunks(width)
+        .take(area.height as usize)
+        .map(|cores| {
+            Line::from(
+                cores
+                    .iter()
+                    .map(|&usage| {
+                        let glyph = SPARK_CHARS[((usage / 100.0).clamp(0.0, 1.0) * 7.0).round() as usize];
+                        Span::styled(glyph.to_string(), Style::default().fg(theme.get_usage_color(usage)))
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect();
+
+    frame.render_widget(ratatui::widgets::Paragraph::new(lines), area);
+}
+
+fn format_short_frequency(freq_mhz: u64) -> String {
+    if freq_mhz >= 1000 {
+        format!("{:.1}G", freq_mhz as f64 / 1000.0)
+    } else {
+        format!("{}M", freq_mhz)
+    }
+}
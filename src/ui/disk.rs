@@ -4,8 +4,9 @@ use crate::ui::Theme;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
+    symbols::Marker,
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph},
     Frame,
 };
 
@@ -28,15 +29,121 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    if app.disk_data.disk_info.is_empty() {
+    if app.disk_data.disk_info.is_empty() && app.disk_data.io_devices.is_empty() {
         let no_disks = Paragraph::new("No disks found")
             .style(Style::default().fg(theme.fg_muted));
         frame.render_widget(no_disks, inner);
         return;
     }
 
+    // I/O stats and graph are only shown where per-device counters exist
+    let has_io = !app.disk_data.io_devices.is_empty();
+    let remaining = inner.height.saturating_sub(2 + has_io as u16);
+    let graph_height = if has_io && remaining >= 4 { remaining / 2 } else { 0 };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(has_io as u16), // I/O stats
+            Constraint::Length(graph_height),  // I/O graph
+            Constraint::Min(0),                // Capacity per mount point
+        ])
+        .split(inner);
+
+    if has_io {
+        draw_io_stats(frame, app, chunks[0], theme);
+    }
+    if graph_height > 0 {
+        draw_io_graph(frame, app, chunks[1], theme);
+    }
+    draw_capacity(frame, app, chunks[2], theme);
+}
+
+fn draw_io_stats(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
+    let mut spans = vec![
+        Span::styled("R ", Style::default().fg(theme.disk_read)),
+        Span::styled(
+            theme.format_speed(app.disk_data.read_rate),
+            Style::default().fg(theme.disk_read).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("  W ", Style::default().fg(theme.disk_write)),
+        Span::styled(
+            theme.format_speed(app.disk_data.write_rate),
+            Style::default().fg(theme.disk_write).add_modifier(Modifier::BOLD),
+        ),
+    ];
+
+    if let Some(device) = app.disk_data.busiest_device() {
+        spans.push(Span::styled(" │ ", Style::default().fg(theme.border)));
+        spans.push(Span::styled(
+            format!("{} ", device.name),
+            Style::default().fg(theme.fg_muted),
+        ));
+        spans.push(Span::styled(
+            format!("{:.0}%", device.busy_percent),
+            Style::default().fg(theme.get_usage_color(device.busy_percent)),
+        ));
+        spans.push(Span::styled(
+            format!(" {} iops", device.read_iops + device.write_iops),
+            Style::default().fg(theme.fg_dim),
+        ));
+    }
+
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+fn draw_io_graph(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
+    let max_read = app.disk_read_history.iter().max().copied().unwrap_or(1024).max(1024) as f64;
+    let max_write = app.disk_write_history.iter().max().copied().unwrap_or(1024).max(1024) as f64;
+    let max_val = max_read.max(max_write);
+
+    let read_data: Vec<(f64, f64)> = app
+        .disk_read_history
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| (i as f64, v as f64))
+        .collect();
+
+    let write_data: Vec<(f64, f64)> = app
+        .disk_write_history
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| (i as f64, v as f64))
+        .collect();
+
+    let datasets = vec![
+        Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(theme.disk_read))
+            .data(&read_data),
+        Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(theme.disk_write))
+            .data(&write_data),
+    ];
+
+    let chart = Chart::new(datasets)
+        .x_axis(
+            Axis::default()
+                .bounds([0.0, app.disk_read_history.len() as f64])
+                .style(Style::default().fg(theme.border)),
+        )
+        .y_axis(
+            Axis::default()
+                .bounds([0.0, max_val])
+                .style(Style::default().fg(theme.border)),
+        )
+        .style(Style::default().bg(theme.bg_secondary));
+
+    frame.render_widget(chart, area);
+}
+
+fn draw_capacity(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
     // Calculate how many disks we can show (2 lines per disk)
-    let max_disks = (inner.height as usize).saturating_sub(1) / 2;
+    let max_disks = area.height as usize / 2;
     let disks_to_show = app.disk_data.disk_info.iter().take(max_disks);
 
     let mut constraints: Vec<Constraint> = Vec::new();
@@ -47,9 +154,8 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .margin(1)
         .constraints(constraints)
-        .split(inner);
+        .split(area);
 
     for (i, disk) in disks_to_show.enumerate() {
         if i >= chunks.len() - 1 {
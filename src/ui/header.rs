@@ -1,14 +1,20 @@
-use crate::app::App;
+use crate::app::{App, TABS};
 use crate::ui::Theme;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph, Tabs},
     Frame,
 };
 
 pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
+    // Small terminals get a two-line header without borders
+    if area.height < 5 {
+        draw_compact(frame, app, area, theme);
+        return;
+    }
+
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(theme.border))
@@ -17,14 +23,41 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Logo, system info and quick stats
+            Constraint::Length(1), // Tab bar
+        ])
+        .split(inner);
+
+    // The logo goes first when space runs out
+    let logo_width = if rows[0].width >= 100 { 20 } else { 0 };
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
-            Constraint::Length(20), // Logo
-            Constraint::Min(20),    // System info
-            Constraint::Length(40), // Quick stats
+            Constraint::Length(logo_width), // Logo
+            Constraint::Min(20),            // System info
+            Constraint::Length(40),         // Quick stats
         ])
-        .split(inner);
+        .split(rows[0]);
+
+    draw_tabs(frame, app, rows[1], theme);
+
+    // Version, or playback status when replaying a recording
+    let subtitle = match app.replay_position() {
+        Some((current, total)) => Span::styled(
+            format!(
+                "{} {}/{} {}x",
+                if app.paused { "⏸" } else { "▶" },
+                current,
+                total,
+                app.replay_speed
+            ),
+            Style::default().fg(theme.warning),
+        ),
+        None => Span::styled("v1.0", Style::default().fg(theme.fg_muted)),
+    };
 
     // Logo/Title
     let logo = Paragraph::new(vec![
@@ -39,23 +72,45 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
         ]),
         Line::from(vec![
             Span::styled("  ", Style::default()),
-            Span::styled("v1.0", Style::default().fg(theme.fg_muted)),
+            subtitle,
         ]),
     ])
     .alignment(Alignment::Left);
     frame.render_widget(logo, chunks[0]);
 
     // System info
+    let mut host_line = vec![
+        Span::styled("  ", Style::default().fg(theme.accent)),
+        Span::styled(&app.hostname, Style::default().fg(theme.fg)),
+        Span::styled("  ", Style::default()),
+    ];
+    if let Some(label) = container_label(app) {
+        host_line.push(Span::styled(
+            format!(" {} ", label),
+            Style::default().fg(theme.bg).bg(theme.accent).add_modifier(Modifier::BOLD),
+        ));
+        host_line.push(Span::styled("  ", Style::default()));
+    }
+    host_line.push(Span::styled(
+        format!("{} {}", app.os_name, app.kernel_version),
+        Style::default().fg(theme.fg_dim),
+    ));
+
+    // Badge for firing alerts
+    let firing = app.alerts.firing_count();
+    if firing > 0 {
+        host_line.push(Span::styled("  ", Style::default()));
+        host_line.push(Span::styled(
+            format!(" ⚠ {} ALERT{} ", firing, if firing == 1 { "" } else { "S" }),
+            Style::default()
+                .fg(theme.bg)
+                .bg(theme.usage_critical)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
     let sys_info = Paragraph::new(vec![
-        Line::from(vec![
-            Span::styled("  ", Style::default().fg(theme.accent)),
-            Span::styled(&app.hostname, Style::default().fg(theme.fg)),
-            Span::styled("  ", Style::default()),
-            Span::styled(
-                format!("{} {}", app.os_name, app.kernel_version),
-                Style::default().fg(theme.fg_dim),
-            ),
-        ]),
+        Line::from(host_line),
         Line::from(vec![
             Span::styled("  ", Style::default().fg(theme.success)),
             Span::styled("Uptime ", Style::default().fg(theme.fg_muted)),
@@ -69,9 +124,10 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
 
     // Quick stats
     let cpu_color = theme.get_usage_color(app.cpu_data.total_usage);
-    let mem_color = theme.get_usage_color(app.memory_data.used_percent);
+    let (_, mem_percent) = app.memory_used();
+    let mem_color = theme.get_usage_color(mem_percent);
 
-    let stats = Paragraph::new(vec![
+    let mut stats = vec![
         Line::from(vec![
             Span::styled("CPU ", Style::default().fg(theme.fg_muted)),
             Span::styled(
@@ -81,7 +137,7 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
             Span::styled("   ", Style::default()),
             Span::styled("MEM ", Style::default().fg(theme.fg_muted)),
             Span::styled(
-                format!("{:5.1}%", app.memory_data.used_percent),
+                format!("{:5.1}%", mem_percent),
                 Style::default().fg(mem_color).add_modifier(Modifier::BOLD),
             ),
         ]),
@@ -98,7 +154,112 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
                 Style::default().fg(theme.success),
             ),
         ]),
-    ])
-    .alignment(Alignment::Right);
+    ];
+    // Task count against the cgroup's pids.max
+    if let (Some(current), Some(max)) = (app.cgroup_data.pids_current, app.cgroup_data.pids_max) {
+        stats[1].spans.push(Span::styled("  PIDS ", Style::default().fg(theme.fg_muted)));
+        stats[1].spans.push(Span::styled(
+            format!("{}/{}", current, max),
+            Style::default().fg(theme.get_usage_color(current as f64 / max.max(1) as f64 * 100.0)),
+        ));
+    }
+    let stats = Paragraph::new(stats).alignment(Alignment::Right);
     frame.render_widget(stats, chunks[2]);
 }
+
+fn draw_compact(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Host and quick stats
+            Constraint::Length(1), // Tab bar
+        ])
+        .split(area);
+
+    let (_, mem_percent) = app.memory_used();
+    let mut spans = vec![
+        Span::styled(
+            "SYSMON ",
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(&app.hostname, Style::default().fg(theme.fg)),
+    ];
+    if let Some(label) = container_label(app) {
+        spans.push(Span::styled(
+            format!(" [{}]", label.to_lowercase()),
+            Style::default().fg(theme.accent),
+        ));
+    }
+    spans.extend([
+        Span::styled("  CPU ", Style::default().fg(theme.fg_muted)),
+        Span::styled(
+            format!("{:.1}%", app.cpu_data.total_usage),
+            Style::default()
+                .fg(theme.get_usage_color(app.cpu_data.total_usage))
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("  MEM ", Style::default().fg(theme.fg_muted)),
+        Span::styled(
+            format!("{:.1}%", mem_percent),
+            Style::default()
+                .fg(theme.get_usage_color(mem_percent))
+                .add_modifier(Modifier::BOLD),
+        ),
+    ]);
+
+    if let Some((current, total)) = app.replay_position() {
+        spans.push(Span::styled(
+            format!("  {} {}/{}", if app.paused { "⏸" } else { "▶" }, current, total),
+            Style::default().fg(theme.warning),
+        ));
+    }
+
+    let firing = app.alerts.firing_count();
+    if firing > 0 {
+        spans.push(Span::styled("  ", Style::default()));
+        spans.push(Span::styled(
+            format!(" ⚠ {} ", firing),
+            Style::default()
+                .fg(theme.bg)
+                .bg(theme.usage_critical)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    let summary = Paragraph::new(Line::from(spans))
+        .style(Style::default().bg(theme.bg_secondary));
+    frame.render_widget(summary, rows[0]);
+
+    draw_tabs(frame, app, rows[1], theme);
+}
+
+fn draw_tabs(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
+    let titles = TABS
+        .iter()
+        .enumerate()
+        .map(|(i, name)| Line::from(format!("{} {}", i + 1, name)));
+
+    let tabs = Tabs::new(titles)
+        .select(app.selected_tab)
+        .style(Style::default().fg(theme.fg_muted))
+        .highlight_style(
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+        )
+        .divider(Span::styled("│", Style::default().fg(theme.border)))
+        .padding(" ", " ");
+
+    frame.render_widget(tabs, area);
+}
+
+/// "CONTAINER" inside a container, "CGROUP" for a limited cgroup on the host
+fn container_label(app: &App) -> Option<&'static str> {
+    if app.cgroup_data.in_container {
+        Some("CONTAINER")
+    } else if app.cgroup_data.is_limited() {
+        Some("CGROUP")
+    } else {
+        None
+    }
+}
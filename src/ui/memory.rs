@@ -1,9 +1,9 @@
 use crate::app::App;
-use crate::data::MemoryData;
+use crate::data::{DiskData, MemoryData, MemoryDetails};
 use crate::ui::Theme;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Modifier, Style},
+    style::{Color, Modifier, Style},
     symbols::Marker,
     text::{Line, Span},
     widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph},
@@ -20,7 +20,10 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
                     .fg(theme.mem_color)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled(" ", Style::default()),
+            Span::styled(
+                format!(" {} ", app.used_memory.label()),
+                Style::default().fg(theme.fg_muted),
+            ),
         ]))
         .borders(Borders::ALL)
         .border_style(Style::default().fg(theme.border))
@@ -42,60 +45,46 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
         ])
         .split(inner);
 
-    let ram_color = theme.get_usage_color(app.memory_data.used_percent);
+    let (used, used_percent) = app.memory_used();
+    let ram_color = theme.get_usage_color(used_percent);
     let swap_color = theme.get_usage_color(app.memory_data.swap_percent);
 
     // RAM stats
     let ram_stats = Line::from(vec![
         Span::styled("RAM ", Style::default().fg(theme.fg_muted)),
         Span::styled(
-            format!("{:.1}%", app.memory_data.used_percent),
+            format!("{:.1}%", used_percent),
             Style::default().fg(ram_color).add_modifier(Modifier::BOLD),
         ),
         Span::styled(" │ ", Style::default().fg(theme.border)),
         Span::styled(
             format!(
-                "{} / {}",
-                MemoryData::format_bytes(app.memory_data.used),
-                MemoryData::format_bytes(app.memory_data.total)
+                "{} / {}{}",
+                MemoryData::format_bytes(used),
+                MemoryData::format_bytes(app.memory_data.total),
+                if app.memory_data.cgroup_limited { " limit" } else { "" }
             ),
             Style::default().fg(theme.fg_dim),
         ),
     ]);
     frame.render_widget(Paragraph::new(ram_stats), chunks[0]);
 
-    // RAM breakdown (available, cached)
-    let mut breakdown_spans = vec![
-        Span::styled("    ", Style::default()),
-        Span::styled("Avail ", Style::default().fg(theme.fg_muted)),
-        Span::styled(
-            MemoryData::format_bytes(app.memory_data.available),
-            Style::default().fg(theme.success),
-        ),
-    ];
+    // Where the memory goes, where the kernel says
+    let mut graph_area = chunks[5];
+    if let Some(details) = &app.memory_data.details {
+        let segments = composition(&app.memory_data, details, theme);
+        draw_composition(frame, &segments, app.memory_data.total, chunks[1], chunks[2], theme);
 
-    if app.memory_data.cached > 0 {
-        breakdown_spans.push(Span::styled(" │ ", Style::default().fg(theme.border)));
-        breakdown_spans.push(Span::styled("Cache ", Style::default().fg(theme.fg_muted)));
-        breakdown_spans.push(Span::styled(
-            MemoryData::format_bytes(app.memory_data.cached),
-            Style::default().fg(theme.fg_dim),
-        ));
+        // Write-back state above the graph, if there's room for both
+        if graph_area.height >= 3 {
+            frame.render_widget(Paragraph::new(writeback_line(details, theme)), graph_area);
+            graph_area.y += 1;
+            graph_area.height -= 1;
+        }
+    } else {
+        draw_simple(frame, app, used_percent, chunks[1], chunks[2], theme);
     }
 
-    frame.render_widget(Paragraph::new(Line::from(breakdown_spans)), chunks[1]);
-
-    // RAM bar
-    let bar_width = chunks[2].width.saturating_sub(2) as usize;
-    let ram_filled = ((app.memory_data.used_percent / 100.0) * bar_width as f64) as usize;
-    let ram_empty = bar_width.saturating_sub(ram_filled);
-
-    let ram_bar = Line::from(vec![
-        Span::styled("▓".repeat(ram_filled), Style::default().fg(ram_color)),
-        Span::styled("░".repeat(ram_empty), Style::default().fg(theme.border)),
-    ]);
-    frame.render_widget(Paragraph::new(ram_bar), chunks[2]);
-
     // Swap stats
     let swap_stats = Line::from(vec![
         Span::styled("SWP ", Style::default().fg(theme.fg_muted)),
@@ -116,6 +105,7 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
     frame.render_widget(Paragraph::new(swap_stats), chunks[3]);
 
     // Swap bar
+    let bar_width = chunks[4].width.saturating_sub(2) as usize;
     let swap_filled = ((app.memory_data.swap_percent / 100.0) * bar_width as f64) as usize;
     let swap_empty = bar_width.saturating_sub(swap_filled);
 
@@ -133,7 +123,7 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
         .map(|(i, &v)| (i as f64, v))
         .collect();
 
-    if chunks[5].height >= 2 {
+    if graph_area.height >= 2 {
         let dataset = Dataset::default()
             .marker(Marker::Braille)
             .graph_type(GraphType::Line)
@@ -153,6 +143,132 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
             )
             .style(Style::default().bg(theme.bg_secondary));
 
-        frame.render_widget(chart, chunks[5]);
+        frame.render_widget(chart, graph_area);
+    }
+}
+
+/// Available/cache line and a plain usage bar, where there's no breakdown
+fn draw_simple(frame: &mut Frame, app: &App, used_percent: f64, text_area: Rect, bar_area: Rect, theme: &Theme) {
+    let ram_color = theme.get_usage_color(used_percent);
+
+    // RAM breakdown (available, cached)
+    let mut breakdown_spans = vec![
+        Span::styled("    ", Style::default()),
+        Span::styled("Avail ", Style::default().fg(theme.fg_muted)),
+        Span::styled(
+            MemoryData::format_bytes(app.memory_data.available),
+            Style::default().fg(theme.success),
+        ),
+    ];
+
+    if app.memory_data.cached > 0 {
+        breakdown_spans.push(Span::styled(" │ ", Style::default().fg(theme.border)));
+        breakdown_spans.push(Span::styled("Cache ", Style::default().fg(theme.fg_muted)));
+        breakdown_spans.push(Span::styled(
+            MemoryData::format_bytes(app.memory_data.cached),
+            Style::default().fg(theme.fg_dim),
+        ));
+    }
+
+    frame.render_widget(Paragraph::new(Line::from(breakdown_spans)), text_area);
+
+    // RAM bar
+    let bar_width = bar_area.width.saturating_sub(2) as usize;
+    let ram_filled = ((used_percent / 100.0) * bar_width as f64) as usize;
+    let ram_empty = bar_width.saturating_sub(ram_filled);
+
+    let ram_bar = Line::from(vec![
+        Span::styled("▓".repeat(ram_filled), Style::default().fg(ram_color)),
+        Span::styled("░".repeat(ram_empty), Style::default().fg(theme.border)),
+    ]);
+    frame.render_widget(Paragraph::new(ram_bar), bar_area);
+}
+
+/// Memory by use, in bar order, with free memory left out
+fn composition(mem: &MemoryData, details: &MemoryDetails, theme: &Theme) -> Vec<(&'static str, u64, Color)> {
+    let kernel = details.kernel_stack + details.page_tables + details.slab_unreclaimable;
+    let mut segments = vec![
+        ("app", details.anon, theme.mem_color),
+        ("shm", details.shmem, theme.swap_color),
+        ("kern", kernel, theme.disk_write),
+        ("huge", details.hugepages_total, theme.warning),
+        ("other", 0, theme.fg_muted),
+        ("slab", details.slab_reclaimable, theme.usage_medium),
+        ("buf", details.buffers, theme.disk_read),
+        ("cache", mem.cached.saturating_sub(details.shmem), theme.cpu_color),
+    ];
+
+    // Whatever the counters above don't account for (vmalloc, per-CPU, drivers...)
+    let accounted: u64 = segments.iter().map(|(_, bytes, _)| bytes).sum();
+    segments[4].1 = mem.total.saturating_sub(mem.free).saturating_sub(accounted);
+
+    segments
+}
+
+/// Legend on the first line, stacked bar on the second (free memory left empty)
+fn draw_composition(
+    frame: &mut Frame,
+    segments: &[(&'static str, u64, Color)],
+    total: u64,
+    legend_area: Rect,
+    bar_area: Rect,
+    theme: &Theme,
+) {
+    // Biggest first, dropping whatever doesn't fit in whole
+    let mut entries: Vec<_> = segments.iter().filter(|(_, bytes, _)| *bytes > 0).collect();
+    entries.sort_by_key(|(_, bytes, _)| std::cmp::Reverse(*bytes));
+
+    let mut legend = Vec::new();
+    let mut width = 0;
+    for (label, bytes, color) in entries {
+        let text = format!("{} {} ", label, DiskData::format_bytes(*bytes));
+        width += text.chars().count() + 1;
+        if width > legend_area.width as usize {
+            break;
+        }
+        legend.push(Span::styled("■", Style::default().fg(*color)));
+        legend.push(Span::styled(text, Style::default().fg(theme.fg_dim)));
+    }
+    frame.render_widget(Paragraph::new(Line::from(legend)), legend_area);
+
+    let bar_width = bar_area.width.saturating_sub(2) as usize;
+    let scale = |bytes: u64| bytes as f64 / total.max(1) as f64;
+
+    let mut bar = Vec::new();
+    let mut cumulative = 0;
+    let mut filled = 0;
+    for (_, bytes, color) in segments {
+        // Round the running total so segments never add up past the bar
+        cumulative += bytes;
+        let end = (scale(cumulative) * bar_width as f64).round() as usize;
+        bar.push(Span::styled("▓".repeat(end.saturating_sub(filled)), Style::default().fg(*color)));
+        filled = filled.max(end);
+    }
+    bar.push(Span::styled("░".repeat(bar_width.saturating_sub(filled)), Style::default().fg(theme.border)));
+    frame.render_widget(Paragraph::new(Line::from(bar)), bar_area);
+}
+
+/// "Dirty 12.0M │ Writeback 0B │ Huge 0B free of 2.0G"
+fn writeback_line<'a>(details: &MemoryDetails, theme: &Theme) -> Line<'a> {
+    let sep = || Span::styled(" │ ", Style::default().fg(theme.border));
+    let mut spans = vec![
+        Span::styled("Dirty ", Style::default().fg(theme.fg_muted)),
+        Span::styled(DiskData::format_bytes(details.dirty), Style::default().fg(theme.fg_dim)),
+        sep(),
+        Span::styled("Writeback ", Style::default().fg(theme.fg_muted)),
+        Span::styled(DiskData::format_bytes(details.writeback), Style::default().fg(theme.fg_dim)),
+    ];
+    if details.hugepages_total > 0 {
+        spans.push(sep());
+        spans.push(Span::styled("Huge ", Style::default().fg(theme.fg_muted)));
+        spans.push(Span::styled(
+            format!(
+                "{} free of {}",
+                DiskData::format_bytes(details.hugepages_free),
+                DiskData::format_bytes(details.hugepages_total)
+            ),
+            Style::default().fg(theme.fg_dim),
+        ));
     }
+    Line::from(spans)
 }
@@ -1,102 +1,268 @@
+mod alerts;
+mod color;
 mod connections;
 mod cpu;
 mod disk;
 mod header;
 mod memory;
 mod network;
+mod pressure;
 mod process;
+mod sensors;
+mod summary;
 mod system_info;
 mod theme;
 
 use crate::app::{App, KillSignal};
+use crate::layout::PanelKind;
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Margin, Rect},
+    layout::{Constraint, Direction, Layout, Margin, Position, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
     Frame,
 };
 
-pub use theme::Theme;
+pub use color::ColorDepth;
+pub use theme::{Theme, ThemeSet};
+
+// Below this the UI is replaced by a "too small" notice
+const MIN_WIDTH: u16 = 40;
+const MIN_HEIGHT: u16 = 12;
+// Below either of these the header shrinks to two borderless lines
+const COMPACT_WIDTH: u16 = 80;
+const COMPACT_HEIGHT: u16 = 30;
+// Rows a table keeps before compact summaries start being dropped
+const MIN_LIST_HEIGHT: u16 = 5;
+
+/// Something placed on the overview tab
+enum Slot {
+    Panel(PanelKind),
+    Summary(Vec<PanelKind>),  // One line per panel
+}
 
 pub fn draw(frame: &mut Frame, app: &App) {
+    let plain;
     let theme = if app.no_color {
-        Theme::plain()
+        plain = Theme::plain();
+        &plain
     } else {
-        Theme::default()
+        app.themes.current()
     };
 
     // Draw background
     let bg_block = Block::default().style(Style::default().bg(theme.bg));
     frame.render_widget(bg_block, frame.area());
 
-    // Main layout with margin for breathing room
-    let outer_area = frame.area().inner(Margin {
+    if frame.area().width < MIN_WIDTH || frame.area().height < MIN_HEIGHT {
+        draw_too_small(frame, theme);
+        return;
+    }
+
+    let (header_area, content_area) = split_screen(frame.area());
+
+    // Draw header
+    header::draw(frame, app, header_area, theme);
+
+    match app.selected_tab {
+        1 => process::draw(frame, app, content_area, theme),
+        2 => draw_network_tab(frame, app, content_area, theme),
+        3 => draw_disk_tab(frame, app, content_area, theme),
+        _ => draw_overview(frame, app, content_area, theme),
+    }
+
+    // Draw kill confirmation dialog if active
+    if let Some((pid, name, signal)) = &app.kill_confirm {
+        draw_kill_dialog(frame, *pid, name, *signal, theme);
+    }
+
+    // Draw status message if present
+    if let Some((message, _)) = &app.status_message {
+        draw_status_message(frame, message, theme);
+    }
+
+    // Draw help screen if active
+    if app.show_help {
+        draw_help_screen(frame, theme);
+    }
+}
+
+/// Split the screen into the header and the selected tab's area
+fn split_screen(area: Rect) -> (Rect, Rect) {
+    // Margin for breathing room
+    let outer_area = area.inner(Margin {
         vertical: 0,
         horizontal: 1,
     });
 
-    // Main vertical layout: Header | Widgets Grid | Processes
+    let compact = area.width < COMPACT_WIDTH || area.height < COMPACT_HEIGHT;
+
+    // Header (with tab bar) | Selected tab
     let main_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(5),  // Header
-            Constraint::Length(11), // Top row (CPU + Memory)
-            Constraint::Length(9),  // Bottom row (Network + System)
-            Constraint::Min(8),     // Processes
+            Constraint::Length(if compact { 2 } else { 5 }), // Header
+            Constraint::Min(0),                              // Tab content
         ])
         .split(outer_area);
 
-    // Draw header
-    header::draw(frame, app, main_chunks[0], &theme);
+    (main_chunks[0], main_chunks[1])
+}
+
+/// Index of the process list row drawn at `position`, for mouse selection
+pub fn process_row_at(app: &App, area: Rect, position: Position) -> Option<usize> {
+    let (_, content_area) = split_screen(area);
+
+    let panel = match app.selected_tab {
+        0 if !app.show_connections && !app.show_alerts => arrange_overview(app, content_area)
+            .into_iter()
+            .find(|(slot, _)| matches!(slot, Slot::Panel(PanelKind::Processes)))?
+            .1,
+        1 => content_area,
+        _ => return None,
+    };
+
+    // Rows start below the top border and the column header
+    let first_row = panel.y + 2;
+    let last_row = panel.bottom().saturating_sub(1);
+    if !panel.contains(position) || position.y < first_row || position.y >= last_row {
+        return None;
+    }
+
+    Some((position.y - first_row) as usize)
+}
+
+/// Place the configured panels, collapsing them when the layout doesn't fit
+fn arrange_overview(app: &App, area: Rect) -> Vec<(Slot, Rect)> {
+    if app.layout.fits(area) {
+        return app
+            .layout
+            .split(area)
+            .into_iter()
+            .map(|(kind, panel_area)| (Slot::Panel(kind), panel_area))
+            .collect();
+    }
 
-    // Top row: CPU | Memory
-    let top_row = Layout::default()
-        .direction(Direction::Horizontal)
+    // One summary line per panel, with the first table taking what's left
+    let kinds = app.layout.kinds();
+    let list = kinds.iter().copied().find(|kind| kind.is_list());
+    let mut summaries: Vec<PanelKind> = kinds.into_iter().filter(|kind| !kind.is_list()).collect();
+    if list.is_some() {
+        // Drop trailing summaries rather than squeeze the table below a usable size
+        summaries.truncate(area.height.saturating_sub(MIN_LIST_HEIGHT) as usize);
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
         .constraints([
-            Constraint::Percentage(50),
-            Constraint::Percentage(50),
+            Constraint::Length(summaries.len() as u16), // Summaries
+            Constraint::Min(0),                         // Table
         ])
-        .split(main_chunks[1]);
+        .split(area);
+
+    let mut slots = Vec::new();
+    if !summaries.is_empty() {
+        slots.push((Slot::Summary(summaries), chunks[0]));
+    }
+    if let Some(kind) = list {
+        slots.push((Slot::Panel(kind), chunks[1]));
+    }
+    slots
+}
 
-    cpu::draw(frame, app, top_row[0], &theme);
-    memory::draw(frame, app, top_row[1], &theme);
+/// The configurable panel grid
+fn draw_overview(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
+    for (slot, panel_area) in arrange_overview(app, area) {
+        let kind = match slot {
+            Slot::Panel(kind) => kind,
+            Slot::Summary(kinds) => {
+                summary::draw(frame, app, panel_area, &kinds, theme);
+                continue;
+            }
+        };
+
+        match kind {
+            PanelKind::Cpu => cpu::draw(frame, app, panel_area, theme),
+            PanelKind::Memory => memory::draw(frame, app, panel_area, theme),
+            PanelKind::Network => network::draw(frame, app, panel_area, theme),
+            PanelKind::Disk => disk::draw(frame, app, panel_area, theme),
+            PanelKind::System => system_info::draw(frame, app, panel_area, theme),
+            PanelKind::Sensors => sensors::draw(frame, app, panel_area, theme),
+            PanelKind::Pressure => pressure::draw(frame, app, panel_area, theme),
+            PanelKind::Connections => connections::draw(frame, app, panel_area, theme),
+            PanelKind::Alerts => alerts::draw(frame, app, panel_area, theme),
+            // Processes, Connections ('c') or Alerts ('a')
+            PanelKind::Processes if app.show_alerts => alerts::draw(frame, app, panel_area, theme),
+            PanelKind::Processes if app.show_connections => {
+                connections::draw(frame, app, panel_area, theme)
+            }
+            PanelKind::Processes => process::draw(frame, app, panel_area, theme),
+        }
+    }
+}
 
-    // Bottom row: Network | Disk | System Info
-    let bottom_row = Layout::default()
-        .direction(Direction::Horizontal)
+/// Network graph above the full connection table
+fn draw_network_tab(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
         .constraints([
-            Constraint::Percentage(33),
-            Constraint::Percentage(34),
-            Constraint::Percentage(33),
+            Constraint::Percentage(40), // Network
+            Constraint::Min(8),         // Connections
         ])
-        .split(main_chunks[2]);
+        .split(area);
 
-    network::draw(frame, app, bottom_row[0], &theme);
-    disk::draw(frame, app, bottom_row[1], &theme);
-    system_info::draw(frame, app, bottom_row[2], &theme);
+    network::draw(frame, app, chunks[0], theme);
+    connections::draw(frame, app, chunks[1], theme);
+}
 
-    // Bottom panel: Processes or Connections (toggle with 'c')
-    if app.show_connections {
-        connections::draw(frame, app, main_chunks[3], &theme);
+/// Disk I/O and capacity beside temperature sensors, batteries and pressure stalls
+fn draw_disk_tab(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
+    // Stack disks above the rest on narrow terminals
+    let (direction, side_direction) = if area.width < COMPACT_WIDTH {
+        (Direction::Vertical, Direction::Horizontal)
     } else {
-        process::draw(frame, app, main_chunks[3], &theme);
-    }
+        (Direction::Horizontal, Direction::Vertical)
+    };
 
-    // Draw kill confirmation dialog if active
-    if let Some((pid, name, signal)) = &app.kill_confirm {
-        draw_kill_dialog(frame, *pid, name, *signal, &theme);
-    }
+    let chunks = Layout::default()
+        .direction(direction)
+        .constraints([
+            Constraint::Percentage(60), // Disks
+            Constraint::Percentage(40), // Sensors and pressure
+        ])
+        .split(area);
 
-    // Draw status message if present
-    if let Some((message, _)) = &app.status_message {
-        draw_status_message(frame, message, &theme);
-    }
+    let side_chunks = Layout::default()
+        .direction(side_direction)
+        .constraints([
+            Constraint::Percentage(50), // Sensors
+            Constraint::Percentage(50), // Pressure
+        ])
+        .split(chunks[1]);
 
-    // Draw help screen if active
-    if app.show_help {
-        draw_help_screen(frame, &theme);
-    }
+    disk::draw(frame, app, chunks[0], theme);
+    sensors::draw(frame, app, side_chunks[0], theme);
+    pressure::draw(frame, app, side_chunks[1], theme);
+}
+
+fn draw_too_small(frame: &mut Frame, theme: &Theme) {
+    let area = frame.area();
+
+    let message = Paragraph::new(vec![
+        Line::from(Span::styled(
+            "Terminal too small",
+            Style::default().fg(theme.warning).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            format!("{}x{} (need {}x{})", area.width, area.height, MIN_WIDTH, MIN_HEIGHT),
+            Style::default().fg(theme.fg_muted),
+        )),
+    ])
+    .alignment(ratatui::layout::Alignment::Center);
+
+    // Vertically centered
+    let y = area.height.saturating_sub(2) / 2;
+    frame.render_widget(message, Rect::new(area.x, area.y + y, area.width, 2.min(area.height)));
 }
 
 fn draw_kill_dialog(frame: &mut Frame, pid: u32, name: &str, signal: KillSignal, theme: &Theme) {
@@ -200,17 +366,6 @@ fn draw_status_message(frame: &mut Frame, message: &str, theme: &Theme) {
 fn draw_help_screen(frame: &mut Frame, theme: &Theme) {
     let area = frame.area();
 
-    // Calculate help dialog size (centered, takes most of the screen)
-    let help_width = 60u16.min(area.width.saturating_sub(4));
-    let help_height = 24u16.min(area.height.saturating_sub(4));
-    let help_x = (area.width.saturating_sub(help_width)) / 2;
-    let help_y = (area.height.saturating_sub(help_height)) / 2;
-
-    let help_area = Rect::new(help_x, help_y, help_width, help_height);
-
-    // Clear the area behind the dialog
-    frame.render_widget(Clear, help_area);
-
     let block = Block::default()
         .title(Line::from(vec![
             Span::styled(" ", Style::default()),
@@ -228,14 +383,19 @@ fn draw_help_screen(frame: &mut Frame, theme: &Theme) {
         ("General", vec![
             ("q, Ctrl+c", "Quit application"),
             ("?", "Toggle this help screen"),
+            ("T", "Cycle color theme"),
         ]),
         ("Navigation", vec![
             ("j, Down", "Move down in process list"),
             ("k, Up", "Move up in process list"),
             ("g", "Go to top of list"),
             ("G", "Go to bottom of list"),
+            ("i", "Cycle network interface"),
+            ("p", "Toggle per-core CPU grid"),
+            ("m", "Used: total-avail / total-free-cache"),
             ("Tab", "Next tab"),
             ("Shift+Tab", "Previous tab"),
+            ("1-4", "Overview / Processes / Network / Disks"),
         ]),
         ("Process Management", vec![
             ("/", "Start filtering processes"),
@@ -243,10 +403,19 @@ fn draw_help_screen(frame: &mut Frame, theme: &Theme) {
             ("s", "Cycle sort column"),
             ("r", "Reverse sort order"),
             ("t", "Toggle tree view"),
+            ("C", "Group processes by cgroup"),
+            ("Enter", "Expand / collapse tree node or cgroup"),
+            ("o", "Only show processes doing I/O"),
             ("c", "Toggle connections view"),
+            ("a", "Toggle alert log"),
             ("x", "Kill process (SIGTERM)"),
             ("X", "Force kill process (SIGKILL)"),
         ]),
+        ("Replay", vec![
+            ("Space", "Pause / resume playback"),
+            ("Left, Right", "Step back / forward one sample"),
+            ("+, -", "Faster / slower playback"),
+        ]),
     ];
 
     let mut lines: Vec<Line> = vec![Line::from("")];
@@ -277,6 +446,17 @@ fn draw_help_screen(frame: &mut Frame, theme: &Theme) {
         Span::styled(" to close", Style::default().fg(theme.fg_muted)),
     ]));
 
+    // Calculate help dialog size (centered, tall enough for every binding)
+    let help_width = 60u16.min(area.width.saturating_sub(4));
+    let help_height = (lines.len() as u16 + 2).min(area.height.saturating_sub(4));
+    let help_x = (area.width.saturating_sub(help_width)) / 2;
+    let help_y = (area.height.saturating_sub(help_height)) / 2;
+
+    let help_area = Rect::new(help_x, help_y, help_width, help_height);
+
+    // Clear the area behind the dialog
+    frame.render_widget(Clear, help_area);
+
     let content = Paragraph::new(lines).block(block);
 
     frame.render_widget(content, help_area);
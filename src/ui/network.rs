@@ -10,6 +10,28 @@ use ratatui::{
 };
 
 pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
+    // Selected interface, or None for the combined view
+    let iface = app
+        .selected_interface
+        .as_ref()
+        .and_then(|name| app.network_data.interface(name));
+
+    let iface_label = match iface {
+        Some(iface) if iface.excluded => format!("[{}, excluded] ", iface.name),
+        Some(iface) => format!("[{}] ", iface.name),
+        None => "[all] ".to_string(),
+    };
+
+    let (speed_down, speed_up, total_received, total_transmitted) = match iface {
+        Some(iface) => (iface.speed_down, iface.speed_up, iface.total_received, iface.total_transmitted),
+        None => (
+            app.network_data.speed_down,
+            app.network_data.speed_up,
+            app.network_data.total_received,
+            app.network_data.total_transmitted,
+        ),
+    };
+
     let block = Block::default()
         .title(Line::from(vec![
             Span::styled(" ", Style::default()),
@@ -20,6 +42,7 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(" ", Style::default()),
+            Span::styled(iface_label, Style::default().fg(theme.fg_muted)),
         ]))
         .borders(Borders::ALL)
         .border_style(Style::default().fg(theme.border))
@@ -37,12 +60,39 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
         ])
         .split(inner);
 
+    // Errors and drops are only shown for a single interface
+    let mut totals_line = vec![
+        Span::styled("Total: ", Style::default().fg(theme.fg_muted)),
+        Span::styled("↓ ", Style::default().fg(theme.fg_dim)),
+        Span::styled(
+            theme.format_bytes(total_received),
+            Style::default().fg(theme.fg_dim),
+        ),
+        Span::styled("  ↑ ", Style::default().fg(theme.fg_dim)),
+        Span::styled(
+            theme.format_bytes(total_transmitted),
+            Style::default().fg(theme.fg_dim),
+        ),
+    ];
+
+    if let Some(iface) = iface {
+        let errors = iface.errors_rx + iface.errors_tx;
+        let drops = iface.drops_rx.unwrap_or(0) + iface.drops_tx.unwrap_or(0);
+        let color = if errors + drops > 0 { theme.warning } else { theme.fg_muted };
+
+        totals_line.push(Span::styled(" │ ", Style::default().fg(theme.border)));
+        totals_line.push(Span::styled(
+            format!("err {} drop {}", errors, drops),
+            Style::default().fg(color),
+        ));
+    }
+
     // Network stats
     let stats = Paragraph::new(vec![
         Line::from(vec![
             Span::styled("↓ ", Style::default().fg(theme.net_down)),
             Span::styled(
-                theme.format_speed(app.network_data.speed_down),
+                theme.format_speed(speed_down),
                 Style::default()
                     .fg(theme.net_down)
                     .add_modifier(Modifier::BOLD),
@@ -50,38 +100,26 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
             Span::styled("    ", Style::default()),
             Span::styled("↑ ", Style::default().fg(theme.net_up)),
             Span::styled(
-                theme.format_speed(app.network_data.speed_up),
+                theme.format_speed(speed_up),
                 Style::default()
                     .fg(theme.net_up)
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
-        Line::from(vec![
-            Span::styled("Total: ", Style::default().fg(theme.fg_muted)),
-            Span::styled("↓ ", Style::default().fg(theme.fg_dim)),
-            Span::styled(
-                theme.format_bytes(app.network_data.total_received),
-                Style::default().fg(theme.fg_dim),
-            ),
-            Span::styled("  ↑ ", Style::default().fg(theme.fg_dim)),
-            Span::styled(
-                theme.format_bytes(app.network_data.total_transmitted),
-                Style::default().fg(theme.fg_dim),
-            ),
-        ]),
+        Line::from(totals_line),
     ]);
     frame.render_widget(stats, chunks[0]);
 
     // Graph
-    let max_down = app
-        .net_down_history
+    let (up_history, down_history) = app.net_history();
+
+    let max_down = down_history
         .iter()
         .max()
         .copied()
         .unwrap_or(1024)
         .max(1024) as f64;
-    let max_up = app
-        .net_up_history
+    let max_up = up_history
         .iter()
         .max()
         .copied()
@@ -89,15 +127,13 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
         .max(1024) as f64;
     let max_val = max_down.max(max_up);
 
-    let down_data: Vec<(f64, f64)> = app
-        .net_down_history
+    let down_data: Vec<(f64, f64)> = down_history
         .iter()
         .enumerate()
         .map(|(i, &v)| (i as f64, v as f64))
         .collect();
 
-    let up_data: Vec<(f64, f64)> = app
-        .net_up_history
+    let up_data: Vec<(f64, f64)> = up_history
         .iter()
         .enumerate()
         .map(|(i, &v)| (i as f64, v as f64))
@@ -119,7 +155,7 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
     let chart = Chart::new(datasets)
         .x_axis(
             Axis::default()
-                .bounds([0.0, app.net_down_history.len() as f64])
+                .bounds([0.0, down_history.len() as f64])
                 .style(Style::default().fg(theme.border)),
         )
         .y_axis(
@@ -0,0 +1,140 @@
+use crate::app::App;
+use crate::data::Pressure;
+use crate::ui::Theme;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph},
+    Frame,
+};
+
+// Graph ceilings, so a quiet system isn't drawn as a wall of noise
+const SCALES: [f64; 6] = [1.0, 5.0, 10.0, 25.0, 50.0, 100.0];
+
+pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
+    let block = Block::default()
+        .title(Line::from(vec![
+            Span::styled(" ", Style::default()),
+            Span::styled(
+                "PRESSURE",
+                Style::default()
+                    .fg(theme.warning)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" avg10 avg60 avg300 ", Style::default().fg(theme.fg_muted)),
+        ]))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border))
+        .style(Style::default().bg(theme.bg_secondary));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    if !app.pressure_data.is_available() {
+        let hint = Paragraph::new(vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                " Pressure stall information is not available",
+                Style::default().fg(theme.fg_muted),
+            )),
+            Line::from(Span::styled(
+                " (needs Linux 4.20+ with PSI enabled)",
+                Style::default().fg(theme.fg_muted),
+            )),
+        ]);
+        frame.render_widget(hint, inner);
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Ratio(1, 3); 3])
+        .split(inner);
+
+    for (((name, pressure), history), chunk) in app
+        .pressure_data
+        .resources()
+        .into_iter()
+        .zip(&app.pressure_history)
+        .zip(chunks.iter())
+    {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(*chunk);
+
+        frame.render_widget(Paragraph::new(resource_line(name, pressure, theme)), rows[0]);
+
+        if rows[1].height == 0 || pressure.is_none() {
+            continue;
+        }
+
+        let data: Vec<(f64, f64)> = history
+            .iter()
+            .enumerate()
+            .map(|(i, &v)| (i as f64, v))
+            .collect();
+        let peak = history.iter().copied().fold(0.0, f64::max);
+        let ceiling = SCALES.iter().copied().find(|&s| peak <= s).unwrap_or(100.0);
+
+        let dataset = Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(pressure_color(peak, theme)))
+            .data(&data);
+
+        let chart = Chart::new(vec![dataset])
+            .x_axis(
+                Axis::default()
+                    .bounds([0.0, history.len() as f64])
+                    .style(Style::default().fg(theme.border)),
+            )
+            .y_axis(
+                Axis::default()
+                    .bounds([0.0, ceiling])
+                    .style(Style::default().fg(theme.border)),
+            )
+            .style(Style::default().bg(theme.bg_secondary));
+
+        frame.render_widget(chart, rows[1]);
+    }
+}
+
+/// "cpu    some  2.21  4.83  3.23  full  0.00  0.00  0.00"
+fn resource_line<'a>(name: &str, pressure: Option<&Pressure>, theme: &Theme) -> Line<'a> {
+    let mut spans = vec![Span::styled(format!(" {:<7}", name), Style::default().fg(theme.fg_dim))];
+
+    let Some(pressure) = pressure else {
+        spans.push(Span::styled("not reported", Style::default().fg(theme.fg_muted)));
+        return Line::from(spans);
+    };
+
+    let averages = std::iter::once(("some", pressure.some)).chain(pressure.full.map(|full| ("full", full)));
+    for (kind, avg) in averages {
+        spans.push(Span::styled(format!("{} ", kind), Style::default().fg(theme.fg_muted)));
+        for value in [avg.avg10, avg.avg60, avg.avg300] {
+            spans.push(Span::styled(
+                format!("{:>6.2}", value),
+                Style::default().fg(pressure_color(value, theme)),
+            ));
+        }
+        spans.push(Span::raw("  "));
+    }
+
+    Line::from(spans)
+}
+
+/// Stall percentages hurt much sooner than usage percentages do
+pub fn pressure_color(percent: f64, theme: &Theme) -> Color {
+    if percent < 1.0 {
+        theme.usage_low
+    } else if percent < 10.0 {
+        theme.usage_medium
+    } else if percent < 25.0 {
+        theme.usage_high
+    } else {
+        theme.usage_critical
+    }
+}
@@ -1,5 +1,5 @@
 use crate::app::App;
-use crate::data::{ProcessData, SortColumn};
+use crate::data::{ProcessData, ProcessInfo, ProcessView, SortColumn};
 use crate::ui::Theme;
 use ratatui::{
     layout::{Constraint, Rect},
@@ -9,6 +9,9 @@ use ratatui::{
     Frame,
 };
 
+// The name column never gets narrower than this
+const MIN_NAME_WIDTH: u16 = 20;
+
 pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
     let filter_indicator = if app.filter_mode {
         format!(" Filter: {}█", app.filter_text)
@@ -23,11 +26,27 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
         SortColumn::Name => "NAME",
         SortColumn::Cpu => "CPU%",
         SortColumn::Memory => "MEM",
+        SortColumn::NetRx => "RX/s",
+        SortColumn::NetTx => "TX/s",
+        SortColumn::DiskRead => "RD/s",
+        SortColumn::DiskWrite => "WR/s",
     };
 
     let sort_arrow = if app.sort_ascending { "↑" } else { "↓" };
 
-    let view_mode = if app.tree_view { " [TREE]" } else { "" };
+    let view_mode = match app.process_view {
+        ProcessView::List => "",
+        ProcessView::Tree => " [TREE]",
+        ProcessView::Cgroups => " [CGROUPS]",
+    };
+    let io_mode = if app.io_only { " [I/O]" } else { "" };
+
+    let count = if app.process_view == ProcessView::Cgroups {
+        let groups = app.process_data.processes.iter().filter(|p| p.group.is_some()).count();
+        format!("{} groups", groups)
+    } else {
+        app.process_data.processes.len().to_string()
+    };
 
     let block = Block::default()
         .title(Line::from(vec![
@@ -39,7 +58,7 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
-                format!(" ({}) ", app.process_data.processes.len()),
+                format!(" ({}) ", count),
                 Style::default().fg(theme.fg_muted),
             ),
             Span::styled(
@@ -50,6 +69,10 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
                 view_mode,
                 Style::default().fg(theme.success),
             ),
+            Span::styled(
+                io_mode,
+                Style::default().fg(theme.disk_color),
+            ),
             Span::styled(
                 filter_indicator,
                 Style::default()
@@ -61,15 +84,24 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
         .border_style(Style::default().fg(theme.border))
         .style(Style::default().bg(theme.bg_secondary));
 
+    // Network and disk rate columns (4..8) are dropped on narrow terminals
+    let show_rates = area.width >= 100;
+    let visible = |i: usize| show_rates || !(4..8).contains(&i);
+
     // Table header
-    let header_cells = ["  PID", "NAME", "CPU%", "MEMORY", "STATUS"]
+    let header_cells = ["  PID", "NAME", "CPU%", "MEMORY", "RX/s", "TX/s", "RD/s", "WR/s", "STATUS"]
         .iter()
         .enumerate()
+        .filter(|(i, _)| visible(*i))
         .map(|(i, h)| {
             let is_sorted = (i == 0 && app.sort_column == SortColumn::Pid)
                 || (i == 1 && app.sort_column == SortColumn::Name)
                 || (i == 2 && app.sort_column == SortColumn::Cpu)
-                || (i == 3 && app.sort_column == SortColumn::Memory);
+                || (i == 3 && app.sort_column == SortColumn::Memory)
+                || (i == 4 && app.sort_column == SortColumn::NetRx)
+                || (i == 5 && app.sort_column == SortColumn::NetTx)
+                || (i == 6 && app.sort_column == SortColumn::DiskRead)
+                || (i == 7 && app.sort_column == SortColumn::DiskWrite);
 
             let style = if is_sorted {
                 Style::default()
@@ -86,6 +118,34 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
         .height(1)
         .bottom_margin(0);
 
+    let widths = [
+        Constraint::Length(8),   // PID
+        Constraint::Min(MIN_NAME_WIDTH),  // Name
+        Constraint::Length(8),   // CPU
+        Constraint::Length(10),  // Memory
+        Constraint::Length(9),   // RX/s
+        Constraint::Length(9),   // TX/s
+        Constraint::Length(9),   // RD/s
+        Constraint::Length(9),   // WR/s
+        Constraint::Length(10),  // Status
+    ];
+    let widths: Vec<Constraint> = widths
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| visible(*i))
+        .map(|(_, width)| width)
+        .collect();
+
+    // What's left for the name column once the fixed columns and spacing are taken
+    let fixed: u16 = widths
+        .iter()
+        .map(|width| match width {
+            Constraint::Length(n) => n + 1,
+            _ => 0,
+        })
+        .sum();
+    let name_width = area.width.saturating_sub(2 + fixed) as usize;
+
     // Table rows
     let processes = app.get_filtered_processes();
     let rows = processes.iter().enumerate().map(|(i, proc)| {
@@ -101,6 +161,10 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
             theme.bg_secondary
         };
 
+        if let Some(group) = &proc.group {
+            return group_row(proc, group.count, group.expanded, row_bg, &visible, theme);
+        }
+
         let status_color = if proc.status.contains("Run") {
             theme.success
         } else {
@@ -108,43 +172,71 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
         };
 
         // Create name cell with tree indentation and/or highlighted filter matches
-        let tree_prefix = if app.tree_view && proc.depth > 0 {
-            let indent = "  ".repeat(proc.depth.min(5)); // Max 5 levels of indent
-            format!("{}└─", indent)
-        } else {
-            String::new()
+        let tree_prefix = match (&proc.tree, app.process_view) {
+            (Some(node), _) => {
+                let marker = match (node.collapsed, node.has_children, proc.depth) {
+                    (Some(_), _, _) => "▸ ",
+                    (None, true, _) => "▾ ",
+                    (None, false, 0) => "  ",
+                    (None, false, _) => " ",
+                };
+                // Deep guides lose their top levels so the name keeps the column's minimum width
+                let budget = name_width.saturating_sub(MIN_NAME_WIDTH as usize + marker.chars().count());
+                format!("{}{}", elide_guide(&node.guide, budget), marker)
+            }
+            (None, ProcessView::Cgroups) => "  ".to_string(),
+            (None, _) => String::new(),
+        };
+        // Number of descendants folded into a collapsed row
+        let folded = match proc.tree.as_ref().and_then(|node| node.collapsed) {
+            Some(hidden) => format!(" (+{})", hidden),
+            None => String::new(),
         };
 
-        let max_name_len = 25usize.saturating_sub(tree_prefix.len());
-        let display_name = format!("{}{}", tree_prefix, truncate_string(&proc.name, max_name_len));
+        let max_name_len = name_width
+            .saturating_sub(tree_prefix.chars().count() + folded.len())
+            .clamp(8, 25);
+        let display_name = format!("{}{}{}", tree_prefix, truncate_string(&proc.name, max_name_len), folded);
+
+        // Collapsed rows show totals for their subtree
+        let totals = if folded.is_empty() { Modifier::empty() } else { Modifier::BOLD };
+
+        // Ancestors kept only to place filter matches in the tree are dimmed
+        let is_context = proc.tree.as_ref().is_some_and(|node| node.context);
+        let name_color = if is_context { theme.fg_muted } else { theme.fg };
 
         let name_cell = if !app.filter_text.is_empty() {
-            Cell::from(highlight_matches(&display_name, &app.filter_text, theme.fg, theme.warning))
+            Cell::from(highlight_matches(&display_name, &app.filter_text, name_color, theme.warning))
         } else {
-            Cell::from(display_name).style(Style::default().fg(theme.fg))
+            Cell::from(display_name).style(Style::default().fg(name_color))
         };
 
         let cells = vec![
             Cell::from(format!("{:>6}", proc.pid)).style(Style::default().fg(theme.fg_dim)),
             name_cell,
             Cell::from(format!("{:>6.1}", proc.cpu_usage))
-                .style(Style::default().fg(cpu_color)),
+                .style(Style::default().fg(cpu_color).add_modifier(totals)),
             Cell::from(format!("{:>8}", ProcessData::format_memory(proc.memory)))
-                .style(Style::default().fg(mem_color)),
+                .style(Style::default().fg(mem_color).add_modifier(totals)),
+            Cell::from(format!("{:>8}", format_rate(proc.net_rx_rate)))
+                .style(Style::default().fg(theme.net_down)),
+            Cell::from(format!("{:>8}", format_rate(proc.net_tx_rate)))
+                .style(Style::default().fg(theme.net_up)),
+            Cell::from(format!("{:>8}", ProcessData::format_memory(proc.disk_read_rate)))
+                .style(Style::default().fg(theme.disk_read)),
+            Cell::from(format!("{:>8}", ProcessData::format_memory(proc.disk_write_rate)))
+                .style(Style::default().fg(theme.disk_write)),
             Cell::from(format_status(&proc.status)).style(Style::default().fg(status_color)),
         ];
+        let cells = cells
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| visible(*i))
+            .map(|(_, cell)| cell);
 
         Row::new(cells).style(Style::default().bg(row_bg))
     });
 
-    let widths = [
-        Constraint::Length(8),   // PID
-        Constraint::Min(20),     // Name
-        Constraint::Length(8),   // CPU
-        Constraint::Length(10),  // Memory
-        Constraint::Length(10),  // Status
-    ];
-
     let table = Table::new(rows, widths)
         .header(header)
         .block(block)
@@ -161,6 +253,57 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
     frame.render_stateful_widget(table, area, &mut state);
 }
 
+/// Keep the last levels of a tree guide that fit in `width` columns, marking the
+/// dropped ones with "…" (e.g. "│ │ │ ├─" becomes "…│ ├─"). The process's own
+/// connector is always kept.
+fn elide_guide(guide: &str, width: usize) -> String {
+    let chars: Vec<char> = guide.chars().collect();
+    if chars.len() <= width {
+        return guide.to_string();
+    }
+
+    // Every level is two columns wide
+    let levels = (width.saturating_sub(1) / 2).max(1);
+    let kept: String = chars[chars.len().saturating_sub(levels * 2)..].iter().collect();
+    format!("…{}", kept)
+}
+
+/// Summary row of a cgroup in the cgroup view
+fn group_row<'a>(
+    proc: &ProcessInfo,
+    count: usize,
+    expanded: bool,
+    row_bg: Color,
+    visible: &dyn Fn(usize) -> bool,
+    theme: &Theme,
+) -> Row<'a> {
+    let marker = if expanded { "▾" } else { "▸" };
+    let bold = |color: Color| Style::default().fg(color).add_modifier(Modifier::BOLD);
+
+    let cells = vec![
+        Cell::from(format!("{:>6}", marker)).style(Style::default().fg(theme.fg_dim)),
+        Cell::from(truncate_string(&proc.name, 25)).style(bold(theme.accent)),
+        Cell::from(format!("{:>6.1}", proc.cpu_usage)).style(bold(theme.get_usage_color(proc.cpu_usage as f64))),
+        Cell::from(format!("{:>8}", ProcessData::format_memory(proc.memory)))
+            .style(bold(theme.get_usage_color(proc.memory_percent))),
+        Cell::from(format!("{:>8}", format_rate(proc.net_rx_rate))).style(Style::default().fg(theme.net_down)),
+        Cell::from(format!("{:>8}", format_rate(proc.net_tx_rate))).style(Style::default().fg(theme.net_up)),
+        Cell::from(format!("{:>8}", ProcessData::format_memory(proc.disk_read_rate)))
+            .style(Style::default().fg(theme.disk_read)),
+        Cell::from(format!("{:>8}", ProcessData::format_memory(proc.disk_write_rate)))
+            .style(Style::default().fg(theme.disk_write)),
+        Cell::from(format!("{} proc{}", count, if count == 1 { "" } else { "s" }))
+            .style(Style::default().fg(theme.fg_dim)),
+    ];
+    let cells = cells
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| visible(*i))
+        .map(|(_, cell)| cell);
+
+    Row::new(cells).style(Style::default().bg(row_bg))
+}
+
 fn truncate_string(s: &str, max_len: usize) -> String {
     if s.len() > max_len {
         format!("{}…", &s[..max_len - 1])
@@ -169,6 +312,13 @@ fn truncate_string(s: &str, max_len: usize) -> String {
     }
 }
 
+fn format_rate(rate: Option<u64>) -> String {
+    match rate {
+        Some(bytes) => ProcessData::format_memory(bytes),
+        None => "-".to_string(),
+    }
+}
+
 fn format_status(status: &str) -> String {
     if status.contains("Run") {
         "Running".to_string()
@@ -0,0 +1,116 @@
+use crate::app::App;
+use crate::data::{BatteryData, TemperatureData};
+use crate::ui::Theme;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
+    let block = Block::default()
+        .title(Line::from(vec![
+            Span::styled(" ", Style::default()),
+            Span::styled(
+                "SENSORS",
+                Style::default()
+                    .fg(theme.disk_color)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" ", Style::default()),
+        ]))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border))
+        .style(Style::default().bg(theme.bg_secondary));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let mut lines: Vec<Line> = vec![Line::from("")];
+
+    // Leave room for the label, reading and a bar at least a few cells wide
+    let label_width = 16usize;
+    let bar_width = (inner.width as usize).saturating_sub(label_width + 12).min(30);
+
+    if app.temperature_data.sensors.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " No temperature sensors found",
+            Style::default().fg(theme.fg_muted),
+        )));
+    }
+
+    for sensor in &app.temperature_data.sensors {
+        let color = temp_color(sensor.temperature, sensor.critical, theme);
+        // Scale against the critical point when known, 100°C otherwise
+        let scale = sensor.critical.unwrap_or(100.0).max(1.0);
+        let filled = ((sensor.temperature / scale).clamp(0.0, 1.0) * bar_width as f32) as usize;
+
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(" {:<width$}", truncate(&sensor.label, label_width - 1), width = label_width),
+                Style::default().fg(theme.fg_dim),
+            ),
+            Span::styled(
+                format!("{:>5.0}°C ", sensor.temperature),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("▓".repeat(filled), Style::default().fg(color)),
+            Span::styled("░".repeat(bar_width - filled), Style::default().fg(theme.border)),
+        ]));
+    }
+
+    if app.battery_data.has_battery {
+        lines.push(Line::from(""));
+        for (i, battery) in app.battery_data.batteries.iter().enumerate() {
+            let time = battery
+                .time_to_empty
+                .or(battery.time_to_full)
+                .map(|t| format!(" ({})", BatteryData::format_time(t)))
+                .unwrap_or_default();
+            let color = if battery.percentage < 20.0 {
+                theme.usage_critical
+            } else if battery.percentage < 50.0 {
+                theme.warning
+            } else {
+                theme.success
+            };
+
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!(" {:<width$}", format!("Battery {}", i), width = label_width),
+                    Style::default().fg(theme.fg_dim),
+                ),
+                Span::styled(
+                    format!("{:>5.0}% ", battery.percentage),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("{}{}", battery.state, time),
+                    Style::default().fg(theme.fg_muted),
+                ),
+            ]));
+        }
+    }
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+fn truncate(s: &str, max_len: usize) -> String {
+    if s.chars().count() > max_len {
+        let truncated: String = s.chars().take(max_len.saturating_sub(1)).collect();
+        format!("{}…", truncated)
+    } else {
+        s.to_string()
+    }
+}
+
+fn temp_color(temp: f32, critical: Option<f32>, theme: &Theme) -> Color {
+    match TemperatureData::get_temp_color_index(temp, critical) {
+        0 => theme.usage_low,
+        1 => theme.usage_medium,
+        2 => theme.usage_high,
+        _ => theme.usage_critical,
+    }
+}
@@ -0,0 +1,162 @@
+use crate::app::App;
+use crate::data::{CpuData, MemoryData};
+use crate::layout::PanelKind;
+use crate::ui::{pressure, Theme};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+    Frame,
+};
+
+const BAR_WIDTH: usize = 10;
+
+/// One line per panel, for terminals too small to draw the panels themselves
+pub fn draw(frame: &mut Frame, app: &App, area: Rect, kinds: &[PanelKind], theme: &Theme) {
+    let lines: Vec<Line> = kinds
+        .iter()
+        .filter_map(|kind| summary_line(app, *kind, theme))
+        .collect();
+
+    let summary = Paragraph::new(lines)
+        .block(Block::default().style(Style::default().bg(theme.bg_secondary)));
+    frame.render_widget(summary, area);
+}
+
+fn summary_line<'a>(app: &'a App, kind: PanelKind, theme: &Theme) -> Option<Line<'a>> {
+    let label = |text: &'static str| Span::styled(format!(" {:<5}", text), Style::default().fg(theme.fg_muted));
+    let sep = || Span::styled(" │ ", Style::default().fg(theme.border));
+
+    let line = match kind {
+        PanelKind::Cpu => {
+            let usage = app.cpu_data.total_usage;
+            let color = theme.get_usage_color(usage);
+            let mut spans = vec![label("CPU")];
+            spans.extend(percent_bar(usage, color, theme));
+            spans.push(sep());
+            spans.push(Span::styled(
+                format!("{} {}", app.cpu_data.format_cores(), CpuData::format_frequency(app.cpu_data.frequency)),
+                Style::default().fg(theme.fg_dim),
+            ));
+            Line::from(spans)
+        }
+        PanelKind::Memory => {
+            let mem = &app.memory_data;
+            let (used, used_percent) = app.memory_used();
+            let color = theme.get_usage_color(used_percent);
+            let mut spans = vec![label("MEM")];
+            spans.extend(percent_bar(used_percent, color, theme));
+            spans.push(sep());
+            spans.push(Span::styled(
+                format!("{} / {}", MemoryData::format_bytes(used), MemoryData::format_bytes(mem.total)),
+                Style::default().fg(theme.fg_dim),
+            ));
+            if mem.swap_total > 0 {
+                spans.push(sep());
+                spans.push(Span::styled(
+                    format!("SWP {:.0}%", mem.swap_percent),
+                    Style::default().fg(theme.get_usage_color(mem.swap_percent)),
+                ));
+            }
+            Line::from(spans)
+        }
+        PanelKind::Network => Line::from(vec![
+            label("NET"),
+            Span::styled("↓ ", Style::default().fg(theme.net_down)),
+            Span::styled(
+                theme.format_speed(app.network_data.speed_down),
+                Style::default().fg(theme.net_down).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("  ↑ ", Style::default().fg(theme.net_up)),
+            Span::styled(
+                theme.format_speed(app.network_data.speed_up),
+                Style::default().fg(theme.net_up).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        PanelKind::Disk => {
+            let mut spans = vec![
+                label("DISK"),
+                Span::styled("R ", Style::default().fg(theme.disk_read)),
+                Span::styled(theme.format_speed(app.disk_data.read_rate), Style::default().fg(theme.disk_read)),
+                Span::styled("  W ", Style::default().fg(theme.disk_write)),
+                Span::styled(theme.format_speed(app.disk_data.write_rate), Style::default().fg(theme.disk_write)),
+            ];
+            // Fullest filesystem, since that is the one about to cause trouble
+            if let Some(disk) = app
+                .disk_data
+                .disk_info
+                .iter()
+                .max_by(|a, b| a.used_percent.total_cmp(&b.used_percent))
+            {
+                spans.push(sep());
+                spans.push(Span::styled(
+                    format!("{} {:.0}%", disk.mount_point, disk.used_percent),
+                    Style::default().fg(theme.get_usage_color(disk.used_percent)),
+                ));
+            }
+            Line::from(spans)
+        }
+        PanelKind::System => {
+            let (l1, l5, l15) = app.load_avg;
+            Line::from(vec![
+                label("LOAD"),
+                Span::styled(format!("{:.2} {:.2} {:.2}", l1, l5, l15), Style::default().fg(theme.fg_dim)),
+                sep(),
+                Span::styled(format!("up {}", app.format_uptime()), Style::default().fg(theme.fg_dim)),
+            ])
+        }
+        PanelKind::Sensors => {
+            let hottest = app
+                .temperature_data
+                .sensors
+                .iter()
+                .max_by(|a, b| a.temperature.total_cmp(&b.temperature));
+            let reading = match hottest {
+                Some(sensor) => format!("{:.0}°C {}", sensor.temperature, sensor.label),
+                None => "n/a".to_string(),
+            };
+            Line::from(vec![label("TEMP"), Span::styled(reading, Style::default().fg(theme.fg_dim))])
+        }
+        PanelKind::Pressure => {
+            let mut spans = vec![label("PSI")];
+            if !app.pressure_data.is_available() {
+                spans.push(Span::styled("n/a", Style::default().fg(theme.fg_dim)));
+            }
+            spans.extend(pressure_spans(app, theme));
+            Line::from(spans)
+        }
+        _ => return None,
+    };
+
+    Some(line)
+}
+
+/// "cpu 2.2 mem 0.0 io 0.1" from the "some" avg10 values
+pub fn pressure_spans(app: &App, theme: &Theme) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    for (name, pressure) in app.pressure_data.resources() {
+        if let Some(pressure) = pressure {
+            let value = pressure.some.avg10;
+            let name = if name == "memory" { "mem" } else { name };
+            spans.push(Span::styled(format!("{} ", name), Style::default().fg(theme.fg_muted)));
+            spans.push(Span::styled(
+                format!("{:.1} ", value),
+                Style::default().fg(pressure::pressure_color(value, theme)),
+            ));
+        }
+    }
+    spans
+}
+
+fn percent_bar(percent: f64, color: ratatui::style::Color, theme: &Theme) -> Vec<Span<'static>> {
+    let filled = ((percent / 100.0).clamp(0.0, 1.0) * BAR_WIDTH as f64).round() as usize;
+    vec![
+        Span::styled(
+            format!("{:5.1}% ", percent),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("▓".repeat(filled), Style::default().fg(color)),
+        Span::styled("░".repeat(BAR_WIDTH - filled), Style::default().fg(theme.border)),
+    ]
+}
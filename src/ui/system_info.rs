@@ -1,6 +1,6 @@
 use crate::app::App;
 use crate::data::{BatteryData, BatteryState, TemperatureData};
-use crate::ui::Theme;
+use crate::ui::{summary, Theme};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
@@ -57,6 +57,13 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
         ),
     ]));
 
+    // Stall percentages say more about saturation than load does, where available
+    if app.pressure_data.is_available() {
+        let mut spans = vec![Span::styled("PSI  ", Style::default().fg(theme.fg_muted))];
+        spans.extend(summary::pressure_spans(app, theme));
+        lines.push(Line::from(spans));
+    }
+
     // Uptime
     lines.push(Line::from(vec![
         Span::styled("Up   ", Style::default().fg(theme.fg_muted)),
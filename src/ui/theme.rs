@@ -1,5 +1,16 @@
+use super::ColorDepth;
+use crate::config::Config;
 use ratatui::style::Color;
+use std::fs;
 
+/// Themes shipped with mprobe, besides the built-in default
+const BUNDLED: [(&str, &str); 3] = [
+    ("gruvbox", include_str!("themes/gruvbox.toml")),
+    ("nord", include_str!("themes/nord.toml")),
+    ("solarized-light", include_str!("themes/solarized-light.toml")),
+];
+
+#[derive(Clone)]
 pub struct Theme {
     // Base colors
     pub bg: Color,
@@ -25,6 +36,8 @@ pub struct Theme {
     pub net_up: Color,
     pub net_down: Color,
     pub disk_color: Color,
+    pub disk_read: Color,
+    pub disk_write: Color,
 
     // Graph colors (gradient from low to high usage)
     pub usage_low: Color,
@@ -67,6 +80,8 @@ impl Default for Theme {
             net_up: Color::Rgb(51, 209, 122),       // #33d17a Green
             net_down: Color::Rgb(53, 132, 228),     // #3584e4 Blue
             disk_color: Color::Rgb(255, 120, 0),    // #ff7800 Orange
+            disk_read: Color::Rgb(255, 163, 72),    // #ffa348 Light orange
+            disk_write: Color::Rgb(192, 28, 40),    // #c01c28 Dark red
 
             // Usage gradient (Adwaita colors)
             usage_low: Color::Rgb(51, 209, 122),    // #33d17a Green
@@ -101,6 +116,8 @@ impl Theme {
             net_up: Color::Green,
             net_down: Color::Cyan,
             disk_color: Color::Yellow,
+            disk_read: Color::Yellow,
+            disk_write: Color::LightRed,
             usage_low: Color::Green,
             usage_medium: Color::Yellow,
             usage_high: Color::LightRed,
@@ -111,6 +128,89 @@ impl Theme {
         }
     }
 
+    /// Parse a theme file; colors it leaves out keep their default
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let table: toml::Table = toml::from_str(content).map_err(|e| e.to_string())?;
+        let mut theme = Self::default();
+
+        for (key, value) in &table {
+            let value = value
+                .as_str()
+                .ok_or_else(|| format!("{}: expected a color string", key))?;
+            let color = parse_color(value).ok_or_else(|| {
+                format!("{}: invalid color '{}' (expected #rrggbb, a color name or 0-255)", key, value)
+            })?;
+
+            let slot = theme
+                .color_mut(key)
+                .ok_or_else(|| format!("unknown theme key '{}'", key))?;
+            *slot = color;
+        }
+
+        Ok(theme)
+    }
+
+    /// The theme with every color mapped to what `depth` can show
+    pub fn quantize(&self, depth: ColorDepth) -> Self {
+        let q = |color: Color| depth.quantize(color);
+        Self {
+            bg: q(self.bg),
+            bg_secondary: q(self.bg_secondary),
+            fg: q(self.fg),
+            fg_dim: q(self.fg_dim),
+            fg_muted: q(self.fg_muted),
+            accent: q(self.accent),
+            border: q(self.border),
+            success: q(self.success),
+            warning: q(self.warning),
+            cpu_color: q(self.cpu_color),
+            mem_color: q(self.mem_color),
+            swap_color: q(self.swap_color),
+            net_up: q(self.net_up),
+            net_down: q(self.net_down),
+            disk_color: q(self.disk_color),
+            disk_read: q(self.disk_read),
+            disk_write: q(self.disk_write),
+            usage_low: q(self.usage_low),
+            usage_medium: q(self.usage_medium),
+            usage_high: q(self.usage_high),
+            usage_critical: q(self.usage_critical),
+            table_header: q(self.table_header),
+            table_row_alt: q(self.table_row_alt),
+            table_selected: q(self.table_selected),
+        }
+    }
+
+    fn color_mut(&mut self, key: &str) -> Option<&mut Color> {
+        Some(match key {
+            "bg" => &mut self.bg,
+            "bg_secondary" => &mut self.bg_secondary,
+            "fg" => &mut self.fg,
+            "fg_dim" => &mut self.fg_dim,
+            "fg_muted" => &mut self.fg_muted,
+            "accent" => &mut self.accent,
+            "border" => &mut self.border,
+            "success" => &mut self.success,
+            "warning" => &mut self.warning,
+            "cpu_color" => &mut self.cpu_color,
+            "mem_color" => &mut self.mem_color,
+            "swap_color" => &mut self.swap_color,
+            "net_up" => &mut self.net_up,
+            "net_down" => &mut self.net_down,
+            "disk_color" => &mut self.disk_color,
+            "disk_read" => &mut self.disk_read,
+            "disk_write" => &mut self.disk_write,
+            "usage_low" => &mut self.usage_low,
+            "usage_medium" => &mut self.usage_medium,
+            "usage_high" => &mut self.usage_high,
+            "usage_critical" => &mut self.usage_critical,
+            "table_header" => &mut self.table_header,
+            "table_row_alt" => &mut self.table_row_alt,
+            "table_selected" => &mut self.table_selected,
+            _ => return None,
+        })
+    }
+
     pub fn get_usage_color(&self, percent: f64) -> Color {
         match percent {
             x if x < 50.0 => self.usage_low,
@@ -157,3 +257,114 @@ impl Theme {
         }
     }
 }
+
+/// Parse "#rrggbb", "#rgb", a color name or a 256-color index
+fn parse_color(value: &str) -> Option<Color> {
+    let value = value.trim();
+
+    // Short hex, e.g. "#fa0"
+    if let Some(hex) = value.strip_prefix('#').filter(|h| h.len() == 3) {
+        let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok().map(|d| d * 17);
+        return Some(Color::Rgb(digit(0)?, digit(1)?, digit(2)?));
+    }
+
+    match value.to_lowercase().as_str() {
+        "default" | "transparent" | "none" => Some(Color::Reset),
+        _ => value.parse().ok(),
+    }
+}
+
+/// The bundled and user themes, and which one is in use
+pub struct ThemeSet {
+    themes: Vec<(String, Theme)>,
+    current: usize,
+}
+
+impl Default for ThemeSet {
+    fn default() -> Self {
+        let mut themes = vec![("adwaita".to_string(), Theme::default())];
+        for (name, content) in BUNDLED {
+            let theme = Theme::from_toml(content).expect("bundled theme is valid");
+            themes.push((name.to_string(), theme));
+        }
+
+        Self { themes, current: 0 }
+    }
+}
+
+impl ThemeSet {
+    /// Load bundled themes plus ~/.config/mprobe/themes/*.toml, fitted to `depth`,
+    /// and select `name`, warning about files that fail to parse
+    pub fn load(name: &str, depth: ColorDepth) -> Self {
+        let mut set = Self::default();
+
+        if let Some(dir) = Config::themes_dir() {
+            let mut paths: Vec<_> = fs::read_dir(&dir)
+                .map(|entries| {
+                    entries
+                        .flatten()
+                        .map(|entry| entry.path())
+                        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+                        .collect()
+                })
+                .unwrap_or_default();
+            paths.sort();
+
+            for path in paths {
+                let Some(theme_name) = path.file_stem().map(|s| s.to_string_lossy().to_lowercase()) else {
+                    continue;
+                };
+
+                let theme = fs::read_to_string(&path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|content| Theme::from_toml(&content));
+
+                match theme {
+                    Ok(theme) => set.insert(theme_name, theme),
+                    Err(e) => eprintln!("Warning: Failed to load theme {}: {}", path.display(), e),
+                }
+            }
+        }
+
+        for (_, theme) in &mut set.themes {
+            *theme = theme.quantize(depth);
+        }
+
+        if !set.select(name) {
+            eprintln!("Warning: Unknown theme '{}', using '{}'", name, set.current_name());
+        }
+
+        set
+    }
+
+    /// Add a theme, replacing any with the same name
+    fn insert(&mut self, name: String, theme: Theme) {
+        match self.themes.iter_mut().find(|(existing, _)| *existing == name) {
+            Some(slot) => slot.1 = theme,
+            None => self.themes.push((name, theme)),
+        }
+    }
+
+    /// Switch to the named theme, returning false if there is none
+    pub fn select(&mut self, name: &str) -> bool {
+        match self.themes.iter().position(|(n, _)| n.eq_ignore_ascii_case(name)) {
+            Some(index) => {
+                self.current = index;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn cycle(&mut self) {
+        self.current = (self.current + 1) % self.themes.len();
+    }
+
+    pub fn current(&self) -> &Theme {
+        &self.themes[self.current].1
+    }
+
+    pub fn current_name(&self) -> &str {
+        &self.themes[self.current].0
+    }
+}